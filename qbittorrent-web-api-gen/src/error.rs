@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// Something in the markdown (or OpenAPI document) describing the api which could not be
+/// parsed. It is reported as a compile error of the derive rather than a panic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The title of the section, e.g. "Get torrent list", or the path of an operation.
+    pub section: Option<String>,
+    /// The line in the markdown (1-based).
+    pub line: Option<usize>,
+    /// What the parser was looking for, e.g. "a method name (`Name: ...`)".
+    pub expected: String,
+}
+
+pub type ParseResult<T> = std::result::Result<T, ParseError>;
+
+impl ParseError {
+    pub fn new(expected: impl Into<String>) -> Self {
+        Self {
+            section: None,
+            line: None,
+            expected: expected.into(),
+        }
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Sets the section unless the error already knows a more specific one.
+    pub fn in_section(mut self, section: Option<&str>) -> Self {
+        if self.section.is_none() {
+            self.section = section.map(str::to_string);
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to parse the api documentation")?;
+        if let Some(line) = self.line {
+            write!(f, " at line {line}")?;
+        }
+        if let Some(section) = &self.section {
+            write!(f, " in section \"{section}\"")?;
+        }
+
+        write!(f, ": expected {}", self.expected)
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -64,7 +64,7 @@ impl<'a> GroupGeneration<'a> {
         let auth = auth_ident();
 
         util::add_docs(
-            self.description(),
+            &util::with_source(self.description(), self.api_group.span),
             quote! {
                 impl super::#auth {
                     pub fn #name_snake(&self) -> #struct_name {
@@ -87,7 +87,7 @@ impl<'a> GroupGeneration<'a> {
         }
     }
 
-    fn group_methods(&self) -> Vec<GroupMethod> {
+    fn group_methods(&self) -> Vec<GroupMethod<'_>> {
         self.methods()
             .iter()
             .map(|method| GroupMethod::new(self, method))
@@ -114,14 +114,35 @@ impl<'a> GroupGeneration<'a> {
         self.derives(self.struct_derives, &[])
     }
 
+    pub fn patch_derives(&self) -> TokenStream {
+        self.derives(self.struct_derives, &["serde::Serialize", "Default"])
+    }
+
+    /// Enums implement (de)serialization manually in order to fall back to `Unknown`
+    /// for values which are not documented.
     pub fn enum_derives(&self) -> TokenStream {
-        self.derives(self.enum_derives, &["PartialEq", "Eq"])
+        self.filtered_derives(
+            self.enum_derives,
+            &["PartialEq", "Eq"],
+            &["serde::Deserialize", "serde::Serialize"],
+        )
     }
 
     pub fn derives(&self, derives: &'a [&'a str], additional_derives: &[&str]) -> TokenStream {
+        self.filtered_derives(derives, additional_derives, &[])
+    }
+
+    fn filtered_derives(
+        &self,
+        derives: &'a [&'a str],
+        additional_derives: &[&str],
+        excluded_derives: &[&str],
+    ) -> TokenStream {
         let derives = self
             .all_derives(derives)
+            .filter(|derive| !additional_derives.contains(derive))
             .chain(additional_derives.iter().copied())
+            .filter(|derive| !excluded_derives.contains(derive))
             .map(|s| syn::parse_str::<syn::Path>(s).unwrap())
             .map(|derive| quote! { #derive });
 
@@ -0,0 +1,257 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::types;
+
+use super::util;
+
+/// Integer fields which are documented as an amount of bytes or a transfer rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteUnit {
+    Bytes,
+    BytesPerSecond,
+}
+
+impl ByteUnit {
+    pub fn type_(&self) -> TokenStream {
+        match self {
+            ByteUnit::Bytes => quote! { super::super::Bytes },
+            ByteUnit::BytesPerSecond => quote! { super::super::BytesPerSecond },
+        }
+    }
+
+    /// The unit of integers described by `description`, e.g. "Torrent size (bytes)".
+    pub fn from_description(description: &str) -> Option<ByteUnit> {
+        let description = description.to_lowercase();
+        if ["bytes/s", "bytes/second", "bytes per second"]
+            .iter()
+            .any(|unit| description.contains(unit))
+        {
+            Some(ByteUnit::BytesPerSecond)
+        } else if ["(bytes)", "in bytes", "amount of data"]
+            .iter()
+            .any(|unit| description.contains(unit))
+        {
+            Some(ByteUnit::Bytes)
+        } else {
+            None
+        }
+    }
+}
+
+impl types::Type {
+    /// The unit of an integer field or parameter, or of the integer values of a map such as
+    /// the per torrent `downloadLimit`.
+    pub fn byte_unit(&self) -> Option<ByteUnit> {
+        let is_integer = match self {
+            types::Type::Number(_) => true,
+            types::Type::Object(types::Object {
+                ref_type: types::RefType::Map(_, value),
+                ..
+            }) => value == "i64",
+            _ => false,
+        };
+
+        if is_integer {
+            ByteUnit::from_description(self.get_type_info().description.as_ref()?)
+        } else {
+            None
+        }
+    }
+}
+
+pub fn generate_bytes() -> TokenStream {
+    let bytes = generate_newtype(
+        "Bytes",
+        "An amount of bytes which is displayed in binary units, e.g. `1.2 GiB`.",
+        "",
+    );
+    let bytes_per_second = generate_newtype(
+        "BytesPerSecond",
+        "A transfer rate in bytes per second which is displayed in binary units, e.g. `350.0 KiB/s`.\n\n\
+        Speed limits use `-1` or `0` for no limit, see [`BytesPerSecond::is_unlimited`]. \
+        Negative values are displayed as `unlimited`.",
+        "/s",
+    );
+
+    quote! {
+        #bytes
+        #bytes_per_second
+
+        impl BytesPerSecond {
+            /// The value qBittorrent uses for speed limits which aren't applied.
+            pub const UNLIMITED: BytesPerSecond = BytesPerSecond(-1);
+
+            /// Whether this is a speed limit which isn't applied. qBittorrent reports these as
+            /// `-1`, e.g. `dl_limit` of a torrent, or as `0`, e.g. `transfer/downloadLimit`.
+            /// Only meaningful for limits, a transfer rate of `0` means that nothing is transferred.
+            pub fn is_unlimited(&self) -> bool {
+                self.0 <= 0
+            }
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct ParseBytesError(String);
+
+        impl std::fmt::Display for ParseBytesError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "invalid amount of bytes: {:?}", self.0)
+            }
+        }
+
+        impl std::error::Error for ParseBytesError {}
+
+        const BINARY_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+        const DECIMAL_UNITS: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+
+        fn format_bytes(bytes: i64, f: &mut std::fmt::Formatter<'_>, suffix: &str) -> std::fmt::Result {
+            // an amount can't be negative, a rate only is for limits which aren't applied
+            if bytes < 0 && !suffix.is_empty() {
+                return f.write_str("unlimited");
+            }
+            // `unsigned_abs` since `abs` overflows for `i64::MIN`
+            let magnitude = bytes.unsigned_abs();
+            if magnitude < 1024 {
+                return write!(f, "{} B{}", bytes, suffix);
+            }
+
+            let mut value = magnitude as f64;
+            let mut unit = 0;
+            while value >= 1024.0 && unit < BINARY_UNITS.len() - 1 {
+                value /= 1024.0;
+                unit += 1;
+            }
+
+            let sign = if bytes < 0 { "-" } else { "" };
+            let precision = f.precision().unwrap_or(1);
+            write!(f, "{}{:.*} {}{}", sign, precision, value, BINARY_UNITS[unit], suffix)
+        }
+
+        /// Parses values such as `1024`, `1.5 GiB` or `20 MB`.
+        fn parse_bytes(input: &str) -> std::result::Result<i64, ParseBytesError> {
+            let error = || ParseBytesError(input.to_string());
+            let trimmed = input.trim();
+            let split_at = trimmed
+                .find(|c: char| c.is_ascii_alphabetic())
+                .unwrap_or(trimmed.len());
+            let (value, unit) = trimmed.split_at(split_at);
+            let value: f64 = value.trim().parse().map_err(|_| error())?;
+            let unit = unit.trim();
+
+            let multiplier = if unit.is_empty() {
+                1.0
+            } else if let Some(exponent) = BINARY_UNITS.iter().position(|u| u.eq_ignore_ascii_case(unit)) {
+                1024f64.powi(exponent as i32)
+            } else if let Some(exponent) = DECIMAL_UNITS.iter().position(|u| u.eq_ignore_ascii_case(unit)) {
+                1000f64.powi(exponent as i32)
+            } else {
+                return Err(error());
+            };
+
+            Ok((value * multiplier).round() as i64)
+        }
+    }
+}
+
+fn generate_newtype(name: &str, docs: &str, suffix: &str) -> TokenStream {
+    let name = util::to_ident(name);
+    let strip_suffix = if suffix.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            if s.trim().eq_ignore_ascii_case("unlimited") {
+                return Ok(Self(-1));
+            }
+            let s = s.trim().strip_suffix(#suffix).unwrap_or(s);
+        }
+    };
+    let newtype = util::add_docs(
+        &Some(docs.to_string()),
+        quote! {
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize)]
+            #[serde(transparent)]
+            pub struct #name(pub i64);
+        },
+    );
+
+    quote! {
+        #newtype
+
+        impl std::fmt::Display for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                format_bytes(self.0, f, #suffix)
+            }
+        }
+
+        impl std::str::FromStr for #name {
+            type Err = ParseBytesError;
+
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                #strip_suffix
+                parse_bytes(s).map(Self)
+            }
+        }
+
+        impl From<i64> for #name {
+            fn from(value: i64) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<#name> for i64 {
+            fn from(value: #name) -> Self {
+                value.0
+            }
+        }
+
+        impl std::ops::Add for #name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl std::ops::AddAssign for #name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl std::ops::Sub for #name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl std::ops::SubAssign for #name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl std::ops::Mul<i64> for #name {
+            type Output = Self;
+
+            fn mul(self, rhs: i64) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl std::ops::Div<i64> for #name {
+            type Output = Self;
+
+            fn div(self, rhs: i64) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+
+        impl std::iter::Sum for #name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|value| value.0).sum())
+            }
+        }
+    }
+}
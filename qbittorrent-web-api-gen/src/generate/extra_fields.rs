@@ -0,0 +1,21 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Fields which are not documented are only kept when the `extra-fields` feature is enabled.
+pub fn is_enabled() -> bool {
+    cfg!(feature = "extra-fields")
+}
+
+/// Collects the fields which are not (yet) documented, e.g. fields added by newer versions
+/// of qBittorrent, instead of dropping them.
+pub fn generate_extra_field() -> TokenStream {
+    if !is_enabled() {
+        return quote! {};
+    }
+
+    quote! {
+        /// Fields which are not (yet) known to this library.
+        #[serde(flatten)]
+        pub extra: std::collections::HashMap<String, serde_json::Value>,
+    }
+}
@@ -0,0 +1,535 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde_json::{json, Value};
+
+use crate::{
+    parser::{self, ReturnType},
+    types,
+};
+
+/// Objects nested deeper than this are sampled as `{}`, which keeps recursive types finite.
+const MAX_DEPTH: usize = 8;
+
+/// A fake qBittorrent whose routes, parameter validation and responses are derived from the
+/// same api groups as the client.
+pub fn generate_fake_server(groups: &[parser::ApiGroup]) -> TokenStream {
+    let definitions = Definitions::new(groups);
+    let methods = groups.iter().flat_map(|group| {
+        group
+            .methods
+            .iter()
+            .map(|method| definitions.generate_method(group, method))
+            .collect::<Vec<_>>()
+    });
+    let server = generate_server();
+
+    quote! {
+        /// A fake qBittorrent derived from the api documentation, e.g. to test code using the
+        /// client without a running instance.
+        pub mod fake {
+            use std::{
+                collections::HashMap,
+                convert::Infallible,
+                net::SocketAddr,
+                sync::{Arc, Mutex},
+            };
+
+            /// The values a parameter accepts.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub enum ParameterKind {
+                Integer,
+                Float,
+                /// `true` or `false`.
+                Bool,
+                String,
+                /// A JSON document, e.g. the `json` parameter of `app/setPreferences`.
+                Json,
+                /// One of the documented values.
+                Enum(&'static [&'static str]),
+            }
+
+            /// A documented parameter of a method.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct FakeParameter {
+                pub name: &'static str,
+                pub is_mandatory: bool,
+                pub kind: ParameterKind,
+            }
+
+            /// A documented method, e.g. `/api/v2/torrents/info`.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct FakeMethod {
+                pub path: &'static str,
+                pub parameters: &'static [FakeParameter],
+                /// A sample of the documented response.
+                pub response: &'static str,
+            }
+
+            /// Every method served by [`FakeServer`] besides login and logout.
+            pub const METHODS: &[FakeMethod] = &[#(#methods),*];
+
+            #server
+        }
+    }
+}
+
+fn generate_server() -> TokenStream {
+    quote! {
+        /// A request received by [`FakeServer`].
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct FakeRequest {
+            pub path: String,
+            pub form: HashMap<String, String>,
+        }
+
+        #[derive(Debug, Default)]
+        struct State {
+            responses: HashMap<String, (u16, String)>,
+            requests: Vec<FakeRequest>,
+        }
+
+        /// Serves [`METHODS`] like qBittorrent does: it checks the credentials and the session
+        /// cookie, rejects unknown, missing or malformed parameters with `400 Bad Request` and
+        /// responds with the sample of the method unless scripted otherwise.
+        #[derive(Debug)]
+        pub struct FakeServer {
+            url: String,
+            state: Arc<Mutex<State>>,
+        }
+
+        impl FakeServer {
+            pub const USERNAME: &'static str = "admin";
+            pub const PASSWORD: &'static str = "adminadmin";
+
+            /// Starts the server on a free port of the current tokio runtime.
+            pub async fn start() -> FakeServer {
+                let state = Arc::new(Mutex::new(State::default()));
+                let service_state = state.clone();
+                let make_service = hyper::service::make_service_fn(move |_| {
+                    let state = service_state.clone();
+                    async move {
+                        Ok::<_, Infallible>(hyper::service::service_fn(move |req| {
+                            handle(state.clone(), req)
+                        }))
+                    }
+                });
+
+                let server = hyper::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+                    .serve(make_service);
+                let url = format!("http://{}", server.local_addr());
+                tokio::spawn(server);
+
+                FakeServer { url, state }
+            }
+
+            pub fn url(&self) -> &str {
+                &self.url
+            }
+
+            /// Responds to `path`, e.g. `/api/v2/torrents/info`, with `body` instead of the
+            /// sample. Parameters are still validated.
+            pub fn respond(&self, path: &str, body: impl Into<String>) {
+                self.script(path, 200, body.into());
+            }
+
+            /// Fails valid requests to `path` with `status`, e.g. `404` for an unknown hash.
+            pub fn fail(&self, path: &str, status: u16) {
+                self.script(path, status, String::new());
+            }
+
+            /// The authenticated requests received so far, oldest first.
+            pub fn requests(&self) -> Vec<FakeRequest> {
+                self.state.lock().unwrap().requests.clone()
+            }
+
+            fn script(&self, path: &str, status: u16, body: String) {
+                self.state
+                    .lock()
+                    .unwrap()
+                    .responses
+                    .insert(path.to_string(), (status, body));
+            }
+        }
+
+        const SID: &str = "SID=fake-session";
+
+        async fn handle(
+            state: Arc<Mutex<State>>,
+            req: hyper::Request<hyper::Body>,
+        ) -> Result<hyper::Response<hyper::Body>, Infallible> {
+            let path = req.uri().path().to_string();
+            let is_authenticated = req
+                .headers()
+                .get_all(hyper::header::COOKIE)
+                .iter()
+                .filter_map(|cookie| cookie.to_str().ok())
+                .any(|cookie| cookie.split(';').any(|c| c.trim() == SID));
+            let content_type = req
+                .headers()
+                .get(hyper::header::CONTENT_TYPE)
+                .and_then(|content_type| content_type.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            let body = hyper::body::to_bytes(req.into_body())
+                .await
+                .unwrap_or_default();
+            let form = parse_form(&content_type, &String::from_utf8_lossy(&body));
+
+            let response = hyper::Response::builder();
+            let (response, status, body) = match path.as_str() {
+                "/api/v2/auth/login" => {
+                    let is_valid = form.get("username").map(String::as_str)
+                        == Some(FakeServer::USERNAME)
+                        && form.get("password").map(String::as_str) == Some(FakeServer::PASSWORD);
+                    if is_valid {
+                        let cookie = format!("{SID}; HttpOnly; path=/");
+                        let response = response.header(hyper::header::SET_COOKIE, cookie);
+                        (response, 200, "Ok.".to_string())
+                    } else {
+                        (response, 200, "Fails.".to_string())
+                    }
+                }
+                _ if !is_authenticated => (response, 403, "Forbidden".to_string()),
+                path => {
+                    let mut state = state.lock().unwrap();
+                    state.requests.push(FakeRequest {
+                        path: path.to_string(),
+                        form: form.clone(),
+                    });
+                    let (status, body) = respond(&state, path, &form);
+                    (response, status, body)
+                }
+            };
+
+            Ok(response
+                .status(status)
+                .body(hyper::Body::from(body))
+                .expect("a valid response"))
+        }
+
+        fn respond(state: &State, path: &str, form: &HashMap<String, String>) -> (u16, String) {
+            if path == "/api/v2/auth/logout" {
+                return (200, String::new());
+            }
+
+            let method = match METHODS.iter().find(|method| method.path == path) {
+                Some(method) => method,
+                None => return (404, "Not Found".to_string()),
+            };
+
+            match validate(method, form) {
+                Ok(()) => state
+                    .responses
+                    .get(path)
+                    .cloned()
+                    .unwrap_or_else(|| (200, method.response.to_string())),
+                Err(message) => (400, message),
+            }
+        }
+
+        /// Checks the parameters of a request against the documented ones.
+        fn validate(method: &FakeMethod, form: &HashMap<String, String>) -> Result<(), String> {
+            let unknown = form
+                .keys()
+                .find(|name| !method.parameters.iter().any(|p| p.name == name.as_str()));
+            if let Some(name) = unknown {
+                return Err(format!("unknown parameter `{name}`"));
+            }
+
+            for parameter in method.parameters {
+                let value = match form.get(parameter.name) {
+                    Some(value) => value,
+                    None if parameter.is_mandatory => {
+                        return Err(format!("missing parameter `{}`", parameter.name))
+                    }
+                    None => continue,
+                };
+
+                let is_valid = match parameter.kind {
+                    ParameterKind::Integer => value.parse::<i64>().is_ok(),
+                    ParameterKind::Float => value.parse::<f64>().is_ok(),
+                    ParameterKind::Bool => value == "true" || value == "false",
+                    ParameterKind::String => true,
+                    ParameterKind::Json => {
+                        serde_json::from_str::<serde_json::Value>(value).is_ok()
+                    }
+                    ParameterKind::Enum(values) => values.contains(&value.as_str()),
+                };
+                if !is_valid {
+                    return Err(format!(
+                        "invalid value `{value}` of parameter `{}`",
+                        parameter.name
+                    ));
+                }
+            }
+
+            Ok(())
+        }
+
+        /// The fields of a `multipart/form-data` or `application/x-www-form-urlencoded` body.
+        fn parse_form(content_type: &str, body: &str) -> HashMap<String, String> {
+            let boundary = match content_type.split_once("boundary=") {
+                Some((_, boundary)) => boundary.trim_matches('"'),
+                None => {
+                    return body
+                        .split('&')
+                        .filter_map(|pair| pair.split_once('='))
+                        .map(|(name, value)| (name.to_string(), value.to_string()))
+                        .collect()
+                }
+            };
+
+            body.split(&format!("--{boundary}"))
+                .filter_map(|part| {
+                    let (headers, value) = part.split_once("\r\n\r\n")?;
+                    let (_, name) = headers.split_once("name=\"")?;
+                    let (name, _) = name.split_once('"')?;
+
+                    Some((name.to_string(), value.trim_end_matches("\r\n").to_string()))
+                })
+                .collect()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Definition<'a> {
+    Object(&'a [types::Type]),
+    Enum(&'a parser::Enum),
+}
+
+/// The objects and enums of all methods by name, since a method may use the types of another
+/// one, e.g. `search/results` and `search/status`.
+#[derive(Debug)]
+struct Definitions<'a> {
+    definitions: HashMap<&'a str, Definition<'a>>,
+}
+
+impl<'a> Definitions<'a> {
+    fn new(groups: &'a [parser::ApiGroup]) -> Self {
+        let mut definitions = HashMap::new();
+
+        for method in groups.iter().flat_map(|group| &group.methods) {
+            let response = method
+                .response_name
+                .as_deref()
+                .zip(method.types.response())
+                .map(|(name, response)| (name, Definition::Object(&response.types)));
+            let objects = method
+                .types
+                .objects()
+                .into_iter()
+                .map(|object| (object.name.as_str(), Definition::Object(&object.types)));
+            let enums = method
+                .types
+                .enums()
+                .into_iter()
+                .map(|enum_| (enum_.name.as_str(), Definition::Enum(enum_)));
+
+            for (name, definition) in response.into_iter().chain(objects).chain(enums) {
+                definitions.entry(name).or_insert(definition);
+            }
+        }
+
+        Self { definitions }
+    }
+
+    fn generate_method(&self, group: &parser::ApiGroup, method: &parser::ApiMethod) -> TokenStream {
+        let path = format!("/api/v2/{}/{}", group.url, method.url);
+        let parameters = method
+            .types
+            .parameters()
+            .into_iter()
+            .map(|parameter| self.generate_parameter(parameter));
+        let json_parameter = method.types.json_parameter().map(|_| {
+            quote! {
+                FakeParameter {
+                    name: "json",
+                    is_mandatory: true,
+                    kind: ParameterKind::Json,
+                }
+            }
+        });
+        let parameters = parameters.chain(json_parameter);
+        let response = self.sample_response(method);
+
+        quote! {
+            FakeMethod {
+                path: #path,
+                parameters: &[#(#parameters),*],
+                response: #response,
+            }
+        }
+    }
+
+    fn generate_parameter(&self, parameter: &types::Type) -> TokenStream {
+        let name = &parameter.get_type_info().name;
+        let is_mandatory = !parameter.is_optional();
+        let kind = match parameter {
+            types::Type::Number(_) => quote! { ParameterKind::Integer },
+            types::Type::Float(_) => quote! { ParameterKind::Float },
+            types::Type::Bool(_) => quote! { ParameterKind::Bool },
+            types::Type::String(_) | types::Type::StringArray(_) => {
+                quote! { ParameterKind::String }
+            }
+            types::Type::JsonObject(_) | types::Type::Object(_) => quote! { ParameterKind::Json },
+            types::Type::Enum(enum_ref) => match self.definitions.get(enum_ref.name.as_str()) {
+                // any path is accepted besides the documented values
+                Some(Definition::Enum(enum_))
+                    if !enum_.values.iter().any(|v| v.is_path_to_download_to()) =>
+                {
+                    let values = enum_.known_values().map(|value| &value.original_value);
+                    quote! { ParameterKind::Enum(&[#(#values),*]) }
+                }
+                _ => quote! { ParameterKind::String },
+            },
+        };
+
+        quote! {
+            FakeParameter {
+                name: #name,
+                is_mandatory: #is_mandatory,
+                kind: #kind,
+            }
+        }
+    }
+
+    /// A body which the client accepts as the response of `method`.
+    fn sample_response(&self, method: &parser::ApiMethod) -> String {
+        if let Some(response) = method.types.response() {
+            let object = self.sample_object(&response.types, 0);
+            let sample = if response.is_list {
+                json!([object])
+            } else {
+                object
+            };
+            return sample.to_string();
+        }
+
+        if let Some(value_response) = method.types.value_response() {
+            return self.sample_type(value_response, 0).to_string();
+        }
+
+        match method.return_type {
+            ReturnType::Unit => "Ok.".into(),
+            ReturnType::Version => "v4.1.3".into(),
+            ReturnType::Integer | ReturnType::Bool => "0".into(),
+            ReturnType::Json | ReturnType::Text => "".into(),
+        }
+    }
+
+    fn sample_object(&self, fields: &[types::Type], depth: usize) -> Value {
+        if depth > MAX_DEPTH {
+            return json!({});
+        }
+
+        fields
+            .iter()
+            .map(|field| {
+                let name = field.get_type_info().name.clone();
+                (name, self.sample_type(field, depth + 1))
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+
+    fn sample_type(&self, type_: &types::Type, depth: usize) -> Value {
+        let sample = match type_ {
+            types::Type::Number(_) => json!(0),
+            types::Type::Float(_) => json!(0.0),
+            types::Type::Bool(_) => json!(false),
+            types::Type::String(_) | types::Type::StringArray(_) => json!(""),
+            types::Type::JsonObject(_) => json!({}),
+            types::Type::Object(object) => match &object.ref_type {
+                types::RefType::String(name) => self.sample(name, depth),
+                types::RefType::Map(_, value) => json!({ "key": self.sample(value, depth) }),
+            },
+            types::Type::Enum(enum_ref) => self.sample(&enum_ref.name, depth),
+        };
+
+        if type_.is_list() {
+            json!([sample])
+        } else {
+            sample
+        }
+    }
+
+    /// A sample of a generated type or of a primitive, e.g. the `i64` of a map.
+    fn sample(&self, name: &str, depth: usize) -> Value {
+        match name {
+            "String" => json!(""),
+            "i64" => json!(0),
+            "f64" => json!(0.0),
+            "bool" => json!(false),
+            _ => match self.definitions.get(name) {
+                Some(Definition::Object(fields)) => self.sample_object(fields, depth),
+                Some(Definition::Enum(enum_)) => sample_enum(enum_),
+                None => json!({}),
+            },
+        }
+    }
+}
+
+fn sample_enum(enum_: &parser::Enum) -> Value {
+    let value = enum_
+        .known_values()
+        .find(|value| !value.is_path_to_download_to());
+
+    match value {
+        Some(value) if enum_.is_numeric() => json!(value.as_integer()),
+        Some(value) => json!(value.original_value),
+        None => json!(""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::md_parser;
+
+    fn groups() -> Vec<parser::ApiGroup> {
+        let content = include_str!("../../api-4_1.md");
+        let token_tree = md_parser::TokenTreeFactory::create(content).unwrap();
+        parser::parse_api_groups(token_tree).unwrap()
+    }
+
+    fn method<'a>(groups: &'a [parser::ApiGroup], url: &str) -> &'a parser::ApiMethod {
+        groups
+            .iter()
+            .flat_map(|group| group.methods.iter().map(move |method| (group, method)))
+            .find(|(group, method)| format!("{}/{}", group.url, method.url) == url)
+            .map(|(_, method)| method)
+            .unwrap()
+    }
+
+    #[test]
+    fn samples_contain_every_documented_field() {
+        let groups = groups();
+        let definitions = Definitions::new(&groups);
+        let info = method(&groups, "torrents/info");
+
+        let sample: Value = serde_json::from_str(&definitions.sample_response(info)).unwrap();
+
+        let torrent = sample[0].as_object().unwrap();
+        for field in &info.types.response().unwrap().types {
+            assert!(torrent.contains_key(&field.get_type_info().name));
+        }
+    }
+
+    #[test]
+    fn samples_of_plain_text_responses() {
+        let groups = groups();
+        let definitions = Definitions::new(&groups);
+
+        let samples: Vec<String> = ["app/version", "transfer/speedLimitsMode", "torrents/pause"]
+            .into_iter()
+            .map(|url| definitions.sample_response(method(&groups, url)))
+            .collect();
+
+        assert_eq!(samples, vec!["v4.1.3", "0", "Ok."]);
+    }
+}
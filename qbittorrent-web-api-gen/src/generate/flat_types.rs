@@ -0,0 +1,58 @@
+use case::CaseExt;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::parser;
+
+use super::util;
+
+/// Re-exports the types of all methods from a single module, e.g. `types::TorrentInfo`
+/// instead of `torrent_management::info::TorrentInfo`.
+pub fn generate_flat_types(groups: &[parser::ApiGroup]) -> TokenStream {
+    let mut names: Vec<String> = Vec::new();
+    let mut reexports = Vec::new();
+
+    for group in groups {
+        for method in &group.methods {
+            for name in method.type_names() {
+                // e.g. `SearchJobStatus` which is documented by both `search/status` and
+                // `search/results`
+                if names.contains(&name) {
+                    continue;
+                }
+
+                let group_name = util::to_ident(&group.name.to_snake());
+                let method_name = util::to_ident(&method.name.to_snake());
+                let type_name = util::to_ident(&name);
+                reexports.push(quote! {
+                    pub use super::#group_name::#method_name::#type_name;
+                });
+                names.push(name);
+            }
+        }
+    }
+
+    quote! {
+        /// All types of the API, e.g. `types::TorrentInfo` or `types::BuildInfo`.
+        pub mod types {
+            pub use super::{Bytes, BytesPerSecond, Version};
+            #(#reexports)*
+        }
+    }
+}
+
+impl parser::ApiMethod {
+    fn type_names(&self) -> Vec<String> {
+        let objects = self.types.objects().into_iter().map(|obj| obj.name.clone());
+        let enums = self.types.enums().into_iter().map(|e| e.name.clone());
+        let json_parameter = self.types.json_parameter().map(|p| p.name.clone());
+
+        self.response_name
+            .clone()
+            .into_iter()
+            .chain(objects)
+            .chain(enums)
+            .chain(json_parameter)
+            .collect()
+    }
+}
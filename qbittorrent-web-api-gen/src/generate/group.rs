@@ -3,7 +3,7 @@ use case::CaseExt;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 
-use super::{api_group::GroupGeneration, util};
+use super::{api_group::GroupGeneration, extra_fields, time, util};
 
 pub fn generate_groups(
     groups: Vec<parser::ApiGroup>,
@@ -15,8 +15,6 @@ pub fn generate_groups(
 
     let gr = groups
         .into_iter()
-        // implemented manually
-        .filter(|group| group.name != "authentication")
         .map(|group| GroupGeneration::new(group, &struct_derives_borrowed, &enum_derive_borrowed))
         .map(generate_group);
 
@@ -52,6 +50,28 @@ impl<'a> StructGenerator<'a> {
             .map(|obj| obj.generate_struct_field());
         let name = util::to_ident(&self.type_.name);
         let derives = self.group.struct_derives();
+        let extra_field = extra_fields::generate_extra_field();
+
+        quote! {
+            #derives
+            #[non_exhaustive]
+            pub struct #name {
+                #(#fields,)*
+                #extra_field
+            }
+        }
+    }
+
+    /// Generates a struct where every field is optional and only the fields which
+    /// are set are serialized, e.g. `PreferencesPatch`.
+    pub fn generate_patch_struct(&self) -> TokenStream {
+        let fields = self
+            .type_
+            .types
+            .iter()
+            .map(|obj| obj.generate_patch_field());
+        let name = util::to_ident(&self.type_.name);
+        let derives = self.group.patch_derives();
 
         quote! {
             #derives
@@ -68,22 +88,54 @@ impl types::Type {
         let type_ = self.owned_type_ident();
         let orig_name = self.name();
 
-        util::add_docs(
-            &self.get_type_info().description,
+        // fields marked as _optional_ may be left out of the response, e.g. partial `sync/maindata` updates
+        let field = if let Some(time_kind) = self.time_kind().filter(|_| time::is_enabled()) {
+            self.generate_time_field(time_kind)
+        } else if self.is_optional() {
+            quote! {
+                #[serde(rename = #orig_name, default)]
+                pub #name_snake: std::option::Option<#type_>
+            }
+        } else {
             quote! {
                 #[serde(rename = #orig_name)]
                 pub #name_snake: #type_
+            }
+        };
+
+        util::add_docs(&self.get_type_info().description, field)
+    }
+
+    fn generate_patch_field(&self) -> TokenStream {
+        let name_snake = self.name_snake();
+        let type_ = self.owned_type_ident();
+        let orig_name = self.name();
+
+        util::add_docs(
+            &self.get_type_info().description,
+            quote! {
+                #[serde(rename = #orig_name, skip_serializing_if = "Option::is_none")]
+                pub #name_snake: std::option::Option<#type_>
             },
         )
     }
 
-    fn owned_type_ident(&self) -> TokenStream {
+    pub fn owned_type_ident(&self) -> TokenStream {
         let owned_type = match self {
-            types::Type::Number(_) => quote! { i64 },
-            types::Type::Float(_) => quote! { f32 },
+            types::Type::Number(type_info) => match self.byte_unit() {
+                Some(byte_unit) => byte_unit.type_(),
+                None => {
+                    let integer_ident = util::to_ident(types::integer_type(&type_info.name));
+                    quote! { #integer_ident }
+                }
+            },
+            types::Type::Float(_) => quote! { f64 },
             types::Type::Bool(_) => quote! { bool },
             types::Type::String(_) => quote! { String },
             types::Type::StringArray(_) => quote! { String },
+            types::Type::JsonObject(_) => {
+                quote! { std::collections::HashMap<String, serde_json::Value> }
+            }
             types::Type::Object(obj) => match &obj.ref_type {
                 types::RefType::String(str) => {
                     let str_ident = &util::to_ident(str);
@@ -91,10 +143,20 @@ impl types::Type {
                 }
                 types::RefType::Map(key, value) => {
                     let key_ident = util::to_ident(key);
-                    let value_ident = util::to_ident(value);
-                    quote! { std::collections::HashMap<#key_ident, #value_ident> }
+                    let value_type = match self.byte_unit() {
+                        Some(byte_unit) => byte_unit.type_(),
+                        None => {
+                            let value_ident = util::to_ident(value);
+                            quote! { #value_ident }
+                        }
+                    };
+                    quote! { std::collections::HashMap<#key_ident, #value_type> }
                 }
             },
+            types::Type::Enum(enum_) => {
+                let enum_ident = util::to_ident(&enum_.name);
+                quote! { #enum_ident }
+            }
         };
 
         if self.is_list() {
@@ -108,7 +170,7 @@ impl types::Type {
         self.get_type_info().name.clone()
     }
 
-    fn name_snake(&self) -> Ident {
+    pub fn name_snake(&self) -> Ident {
         util::to_ident(&self.name().to_snake())
     }
 }
@@ -125,41 +187,224 @@ impl<'a> EnumGeneration<'a> {
     }
 
     pub fn generate(&self) -> TokenStream {
-        let values = self
-            .enum_
-            .values
-            .iter()
-            .map(|enum_value| enum_value.generate());
+        let is_numeric = self.is_numeric();
+        let values = self.values().map(|enum_value| enum_value.generate());
+        let display_arms = self
+            .values()
+            .map(|enum_value| enum_value.generate_display_arm());
         let name = util::to_ident(&self.enum_.name);
         let derives = self.group.enum_derives();
+        let unknown_type = if is_numeric {
+            quote! { i64 }
+        } else {
+            quote! { String }
+        };
+        let serde_impls = if is_numeric {
+            self.generate_numeric_serde()
+        } else {
+            self.generate_string_serde()
+        };
 
         quote! {
             #[allow(clippy::enum_variant_names)]
             #derives
             pub enum #name {
                 #(#values,)*
+                /// A value which is not (yet) known to this library.
+                Unknown(#unknown_type),
+            }
+
+            #serde_impls
+
+            /// Formats the value as it is sent to qBittorrent.
+            impl std::fmt::Display for #name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    match self {
+                        #(#display_arms,)*
+                        Self::Unknown(value) => std::fmt::Display::fmt(value, f),
+                    }
+                }
+            }
+        }
+    }
+
+    fn values(&self) -> impl Iterator<Item = &parser::EnumValue> {
+        self.enum_.known_values()
+    }
+
+    fn is_numeric(&self) -> bool {
+        self.enum_.is_numeric()
+    }
+
+    fn generate_string_serde(&self) -> TokenStream {
+        let name = util::to_ident(&self.enum_.name);
+        let (original_values, variants): (Vec<_>, Vec<_>) = self
+            .values()
+            .map(|value| (value.original_value.clone(), value.name_camel()))
+            .unzip();
+
+        quote! {
+            impl<'de> serde::Deserialize<'de> for #name {
+                fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    let value = <String as serde::Deserialize>::deserialize(deserializer)?;
+                    Ok(match value.as_str() {
+                        #(#original_values => Self::#variants,)*
+                        _ => Self::Unknown(value),
+                    })
+                }
+            }
+
+            impl serde::Serialize for #name {
+                fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    match self {
+                        #(Self::#variants => serializer.serialize_str(#original_values),)*
+                        Self::Unknown(value) => serializer.serialize_str(value),
+                    }
+                }
+            }
+        }
+    }
+
+    fn generate_numeric_serde(&self) -> TokenStream {
+        let name = util::to_ident(&self.enum_.name);
+        let (integers, variants): (Vec<_>, Vec<_>) = self
+            .values()
+            .filter_map(|value| {
+                let integer = value.as_integer()?;
+                Some((
+                    proc_macro2::Literal::i64_unsuffixed(integer),
+                    value.name_camel(),
+                ))
+            })
+            .unzip();
+
+        let has_path = self.values().any(|value| value.is_path_to_download_to());
+
+        let deserialize = if has_path {
+            quote! {
+                #[derive(serde::Deserialize)]
+                #[serde(untagged)]
+                enum Value {
+                    Integer(i64),
+                    Path(String),
+                }
+
+                Ok(match <Value as serde::Deserialize>::deserialize(deserializer)? {
+                    #(Value::Integer(#integers) => Self::#variants,)*
+                    Value::Integer(value) => Self::Unknown(value),
+                    Value::Path(path) => Self::PathToDownloadTo(path),
+                })
+            }
+        } else {
+            quote! {
+                Ok(match <i64 as serde::Deserialize>::deserialize(deserializer)? {
+                    #(#integers => Self::#variants,)*
+                    value => Self::Unknown(value),
+                })
+            }
+        };
+
+        let serialize_path = if has_path {
+            quote! { Self::PathToDownloadTo(path) => serializer.serialize_str(path), }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            impl<'de> serde::Deserialize<'de> for #name {
+                fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    #deserialize
+                }
+            }
+
+            impl serde::Serialize for #name {
+                fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    match self {
+                        #(Self::#variants => serializer.serialize_i64(#integers),)*
+                        #serialize_path
+                        Self::Unknown(value) => serializer.serialize_i64(*value),
+                    }
+                }
             }
         }
     }
 }
 
+// special enum value which does not follow conventions
+const PATH_TO_DOWNLOAD_TO: &str = "\"/path/to/download/to\"";
+
+impl parser::Enum {
+    /// Values which get a variant of their own, any other value ends up in `Unknown`.
+    pub fn known_values(&self) -> impl Iterator<Item = &parser::EnumValue> {
+        self.values.iter().filter(|value| !value.is_unknown())
+    }
+
+    /// Enums where every value is an integer, e.g. tracker `status` or file `priority`,
+    /// which qBittorrent sends as JSON numbers.
+    pub fn is_numeric(&self) -> bool {
+        self.known_values()
+            .all(|value| value.as_integer().is_some() || value.is_path_to_download_to())
+    }
+}
+
 impl parser::EnumValue {
     fn generate(&self) -> TokenStream {
         util::add_docs(&self.description, self.generate_field())
     }
 
-    fn generate_field(&self) -> TokenStream {
+    pub fn as_integer(&self) -> Option<i64> {
+        self.original_value.parse().ok()
+    }
+
+    pub fn is_path_to_download_to(&self) -> bool {
+        self.original_value == PATH_TO_DOWNLOAD_TO
+    }
+
+    /// The placeholder of e.g. `scan_dirs` values, which may be any path besides the integers.
+    pub fn path_to_download_to(description: Option<String>) -> Self {
+        Self::new(PATH_TO_DOWNLOAD_TO, description)
+    }
+
+    // special enum value (e.g. torrent `state`) which is covered by the `Unknown` fallback
+    fn is_unknown(&self) -> bool {
+        self.value == "Unknown"
+    }
+
+    fn generate_display_arm(&self) -> TokenStream {
         let orig_name = self.original_value.clone();
 
-        // special enum value which does not follow conventions
-        if orig_name == "\"/path/to/download/to\"" {
+        if self.is_path_to_download_to() {
+            quote! {
+                Self::PathToDownloadTo(path) => f.write_str(path)
+            }
+        } else {
+            let name_camel = self.name_camel();
+            quote! {
+                Self::#name_camel => f.write_str(#orig_name)
+            }
+        }
+    }
+
+    fn generate_field(&self) -> TokenStream {
+        if self.is_path_to_download_to() {
             quote! {
                 PathToDownloadTo(String)
             }
         } else {
             let name_camel = self.name_camel();
             quote! {
-                #[serde(rename = #orig_name)]
                 #name_camel
             }
         }
@@ -192,9 +437,14 @@ impl types::Type {
     pub fn generate_form_builder(&self, add_to: TokenStream) -> TokenStream {
         let name_str = self.name();
         let name_snake = self.name_snake();
+        // sent as a plain integer instead of e.g. `1.2 GiB`
+        let value = match self.byte_unit() {
+            Some(_) => quote! { #name_snake.0.to_string() },
+            None => quote! { #name_snake.to_string() },
+        };
 
         quote! {
-            #add_to = #add_to.text(#name_str, #name_snake.to_string());
+            #add_to = #add_to.text(#name_str, #value);
         }
     }
 
@@ -212,6 +462,10 @@ impl types::Type {
     }
 
     fn borrowed_type(&self) -> TokenStream {
+        if let Some(byte_unit) = self.byte_unit() {
+            return byte_unit.type_();
+        }
+
         let type_ = self.borrowed_type_ident();
         if self.should_borrow() {
             quote! { &#type_ }
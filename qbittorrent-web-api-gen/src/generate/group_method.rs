@@ -1,9 +1,11 @@
 use crate::parser;
+use case::CaseExt;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 
 use super::{
     api_group::GroupGeneration,
+    extra_fields,
     group::{EnumGeneration, StructGenerator},
     util,
 };
@@ -23,7 +25,9 @@ impl<'a> GroupMethod<'a> {
         let method_name = self.method.name_snake();
         let structs = self.structs();
         let enums = self.enums();
+        let shared = self.shared();
         let builder = self.generate_request_builder();
+        let json_parameter_struct = self.generate_json_parameter_struct();
         let response_struct = self.generate_response_struct();
         let request_method = self.generate_request_method();
 
@@ -31,6 +35,8 @@ impl<'a> GroupMethod<'a> {
             pub mod #method_name {
                 #structs
                 #enums
+                #shared
+                #json_parameter_struct
                 #builder
                 #response_struct
                 #request_method
@@ -61,6 +67,22 @@ impl<'a> GroupMethod<'a> {
         }
     }
 
+    /// Re-exports the types which are defined by other methods, e.g. the enums of
+    /// `preferences` which are used by `setPreferences`.
+    pub fn shared(&self) -> TokenStream {
+        let shared = self.method.types.shared().into_iter().map(|shared| {
+            let group = util::to_ident(&shared.group.to_snake());
+            let method = util::to_ident(&shared.method.to_snake());
+            let name = util::to_ident(&shared.name);
+
+            quote! { pub use super::super::#group::#method::#name; }
+        });
+
+        quote! {
+            #(#shared)*
+        }
+    }
+
     fn generate_request_method(&self) -> TokenStream {
         let method_name = self.method.name_snake();
 
@@ -70,6 +92,7 @@ impl<'a> GroupMethod<'a> {
             .mandatory_params()
             .iter()
             .map(|param| param.to_parameter())
+            .chain(self.json_parameter())
             .collect();
 
         let form_builder = self.mandatory_parameters_as_form_builder();
@@ -96,7 +119,8 @@ impl<'a> GroupMethod<'a> {
         };
 
         let group_struct_name = self.group.struct_name();
-        let method_impl_with_docs = util::add_docs(&self.method.description, method_impl);
+        let docs = util::with_source(&self.method.description, self.method.span);
+        let method_impl_with_docs = util::add_docs(&docs, method_impl);
 
         quote! {
             impl<'a> super::#group_struct_name<'a> {
@@ -105,10 +129,28 @@ impl<'a> GroupMethod<'a> {
         }
     }
 
+    /// Returns a TokenStream containing the struct passed as the `json` parameter,
+    /// e.g. `PreferencesPatch`, otherwise an empty TokenStream is returned.
+    fn generate_json_parameter_struct(&self) -> TokenStream {
+        match self.method.types.json_parameter() {
+            Some(json_parameter) => {
+                StructGenerator::new(json_parameter, self.group).generate_patch_struct()
+            }
+            None => quote! {},
+        }
+    }
+
+    fn json_parameter(&self) -> Option<TokenStream> {
+        let json_parameter = self.method.types.json_parameter()?;
+        let name = util::to_ident(&json_parameter.name);
+
+        Some(quote! { patch: &#name })
+    }
+
     fn generate_response_struct(&self) -> TokenStream {
-        let response = match self.method.types.response() {
-            Some(res) => res,
-            None => return quote! {},
+        let (response, name) = match (self.method.types.response(), self.response_ident()) {
+            (Some(res), Some(name)) => (res, name),
+            _ => return quote! {},
         };
 
         let struct_fields = response
@@ -117,15 +159,23 @@ impl<'a> GroupMethod<'a> {
             .map(|field| field.generate_struct_field());
 
         let derives = self.group.struct_derives();
+        let extra_field = extra_fields::generate_extra_field();
 
         quote! {
             #derives
-            pub struct Response {
+            #[non_exhaustive]
+            pub struct #name {
                 #(#struct_fields,)*
+                #extra_field
             }
         }
     }
 
+    /// The name of the response struct, e.g. `TorrentInfo`.
+    fn response_ident(&self) -> Option<Ident> {
+        self.method.response_name.as_deref().map(util::to_ident)
+    }
+
     /// Returns a TokenStream containing a request builder if there are optional
     /// parameters, otherwise an empty TokenStream is returned.
     fn generate_request_builder(&self) -> TokenStream {
@@ -170,18 +220,25 @@ impl<'a> GroupMethod<'a> {
     ) -> TokenStream {
         let method_url = format!("/api/v2/{}/{}", self.group.url(), self.method.url);
 
-        let (response_type, response_parse) = match self.method.types.response() {
-            Some(resp) => {
-                if resp.is_list {
-                    (
-                        quote! { std::vec::Vec<Response> },
-                        quote! { .json::<std::vec::Vec<Response>>() },
-                    )
-                } else {
-                    (quote! { Response }, quote! { .json::<Response>() })
-                }
-            }
-            None => (quote! { String }, quote! { .text() }),
+        let response_type = match self.method.types.response().zip(self.response_ident()) {
+            Some((resp, name)) if resp.is_list => Some(quote! { std::vec::Vec<#name> }),
+            Some((_, name)) => Some(quote! { #name }),
+            None => self.method.types.value_response().map(|value| {
+                let value_type = value.owned_type_ident();
+                quote! { #value_type }
+            }),
+        };
+
+        let (response_type, response_parse, response_result) = match response_type {
+            Some(response_type) => (
+                response_type,
+                quote! { .error_for_status()?.text() },
+                quote! { super::super::parse_json(#method_url, res) },
+            ),
+            None => self
+                .method
+                .return_type
+                .generate_plain_text(&method_url, &self.method.description),
         };
 
         quote! {
@@ -195,7 +252,7 @@ impl<'a> GroupMethod<'a> {
                     #response_parse
                     .await?;
 
-                Ok(res)
+                #response_result
             }
         }
     }
@@ -208,8 +265,15 @@ impl<'a> GroupMethod<'a> {
             .into_iter()
             .map(|param| param.generate_form_builder(quote! { form }));
 
+        let json_builder = self.method.types.json_parameter().map(|_| {
+            quote! {
+                let form = form.text("json", serde_json::to_string(patch)?);
+            }
+        });
+
         quote! {
             #(let #builder)*
+            #json_builder
         }
     }
 }
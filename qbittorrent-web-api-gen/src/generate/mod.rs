@@ -1,39 +1,119 @@
 mod api_group;
 mod api_method;
+mod bytes;
+mod extra_fields;
+mod fake;
+mod flat_types;
 mod group;
 mod group_method;
+mod return_type;
 mod skeleton;
+mod time;
 mod util;
 
 use case::CaseExt;
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::{md_parser, parser};
+use crate::{error::ParseResult, md_parser, openapi, parser};
 
-use self::{group::generate_groups, skeleton::generate_skeleton};
+use self::{
+    bytes::generate_bytes, fake::generate_fake_server, flat_types::generate_flat_types,
+    group::generate_groups, return_type::generate_plain_text_helpers, skeleton::generate_skeleton,
+    time::generate_serde_time,
+};
 
-pub fn generate(ast: &syn::DeriveInput, api_content: &str) -> TokenStream {
+pub fn generate(ast: &syn::DeriveInput, api_content: &str) -> ParseResult<TokenStream> {
     let ident = &ast.ident;
     let struct_derives = get_derives(ast, "struct_derives");
     let enum_derives = get_derives(ast, "enum_derives");
 
-    let token_tree = md_parser::TokenTreeFactory::create(api_content);
-    let api_groups = parser::parse_api_groups(token_tree);
+    // e.g. `#[api_gen(openapi = "qbittorrent.yaml")]`
+    let openapi_path = get_attribute_values(ast, "openapi")
+        .first()
+        .map(|path| openapi::path(path));
+    let (api_groups, version) = match &openapi_path {
+        Some(path) => {
+            let document = openapi::read(path)?;
+            (
+                openapi::parse_api_groups(&document)?,
+                openapi::api_version(&document),
+            )
+        }
+        None => {
+            #[cfg(feature = "commonmark")]
+            let token_tree = md_parser::TokenTreeFactory::create_commonmark(api_content)?;
+            #[cfg(not(feature = "commonmark"))]
+            let token_tree = md_parser::TokenTreeFactory::create(api_content)?;
+            let version = parser::parse_api_version(&token_tree);
+            (parser::parse_api_groups(token_tree)?, version)
+        }
+    };
+    // tells cargo to rebuild the api when the document changes
+    let openapi_document = openapi_path.map(|path| {
+        let path = path.display().to_string();
+        quote! { const _: &[u8] = include_bytes!(#path); }
+    });
+    let openapi = if cfg!(feature = "openapi") {
+        generate_openapi(&api_groups, version)
+    } else {
+        quote! {}
+    };
+    let api_groups: Vec<parser::ApiGroup> = api_groups
+        .into_iter()
+        // implemented manually
+        .filter(|group| group.name != "authentication")
+        .collect();
 
     let skeleton = generate_skeleton(ident);
+    let serde_time = generate_serde_time();
+    let bytes = generate_bytes();
+    let plain_text_helpers = generate_plain_text_helpers();
+    let flat_types = generate_flat_types(&api_groups);
+    let fake_server = if cfg!(feature = "fake-server") {
+        generate_fake_server(&api_groups)
+    } else {
+        quote! {}
+    };
     let groups = generate_groups(api_groups, struct_derives, enum_derives);
     let impl_ident = syn::Ident::new(&format!("{}_impl", ident).to_snake(), ident.span());
 
-    quote! {
+    Ok(quote! {
         pub mod #impl_ident {
+            #openapi_document
             #skeleton
+            #serde_time
+            #bytes
+            #plain_text_helpers
             #groups
+            #flat_types
+            #openapi
+            #fake_server
         }
+    })
+}
+
+/// The api as an OpenAPI 3 document, including the authentication methods which are
+/// implemented manually.
+fn generate_openapi(api_groups: &[parser::ApiGroup], version: Option<String>) -> TokenStream {
+    let version = version.unwrap_or_else(|| "2".into());
+    let document = openapi::document(api_groups, &version).to_string();
+
+    quote! {
+        /// The API as an OpenAPI 3 document (JSON), e.g. to be used with standard tooling.
+        pub const OPENAPI: &str = #document;
     }
 }
 
 fn get_derives(ast: &syn::DeriveInput, name: &str) -> Vec<String> {
+    get_attribute_values(ast, name)
+        .into_iter()
+        .map(|value| value.split(',').map(|s| s.trim()).collect())
+        .collect()
+}
+
+/// The values of `name` in the `api_gen` attribute, e.g. `#[api_gen(name = "value")]`.
+fn get_attribute_values(ast: &syn::DeriveInput, name: &str) -> Vec<String> {
     ast.attrs
         .iter()
         .find(|attr| {
@@ -67,8 +147,71 @@ fn get_derives(ast: &syn::DeriveInput, name: &str) -> Vec<String> {
         })
         .map(|name_value| name_value.lit)
         .filter_map(|lit| match lit {
-            syn::Lit::Str(str) => Some(str.value().split(',').map(|s| s.trim()).collect()),
+            syn::Lit::Str(str) => Some(str.value()),
             _ => None,
         })
         .collect()
 }
+
+// the features change the generated code, the snapshot is of the default one
+#[cfg(all(
+    test,
+    not(any(
+        feature = "chrono",
+        feature = "time",
+        feature = "extra-fields",
+        feature = "commonmark",
+        feature = "openapi",
+        feature = "fake-server"
+    ))
+))]
+mod tests {
+    use super::*;
+
+    macro_rules! TEST_DIR {
+        () => {
+            "generate_tests"
+        };
+    }
+
+    /// The expanded derive of `pub struct Api;` for the bundled api markdown.
+    fn expand() -> String {
+        let input: syn::DeriveInput = syn::parse_quote!(
+            pub struct Api;
+        );
+        let tokens = generate(&input, include_str!("../../api-4_1.md")).unwrap();
+        let file: syn::File = syn::parse2(tokens).unwrap();
+
+        prettyplease::unparse(&file)
+    }
+
+    #[allow(unused_macros)]
+    macro_rules! run_test {
+        ($test_file:expr) => {
+            use pretty_assertions::assert_eq;
+
+            // when
+            let expanded = expand();
+
+            // then
+            let should_be = include_str!(concat!(TEST_DIR!(), "/", $test_file, ".check"));
+            assert_eq!(expanded, should_be);
+        };
+    }
+
+    // use this macro when creating/updating as test
+    #[allow(unused_macros)]
+    macro_rules! update_test {
+        ($test_file:expr) => {
+            let expanded = expand();
+
+            let file = concat!("src/generate/", TEST_DIR!(), "/", $test_file, ".check");
+            std::fs::write(file, expanded).unwrap();
+        };
+    }
+
+    #[test]
+    fn api() {
+        run_test!("api");
+    }
+}
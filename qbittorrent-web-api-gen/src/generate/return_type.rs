@@ -0,0 +1,155 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::parser::ReturnType;
+
+use super::bytes::ByteUnit;
+
+impl ReturnType {
+    /// The type, the way to read the body and how to turn the body into the type for
+    /// methods without a response table.
+    pub fn generate_plain_text(
+        &self,
+        method_url: &str,
+        description: &Option<String>,
+    ) -> (TokenStream, TokenStream, TokenStream) {
+        // e.g. the global `downloadLimit` in bytes/second
+        let byte_unit = description.as_deref().and_then(ByteUnit::from_description);
+        if let (ReturnType::Integer, Some(byte_unit)) = (self, byte_unit) {
+            let type_ = byte_unit.type_();
+            return (
+                quote! { #type_ },
+                quote! { .error_for_status()?.text() },
+                quote! { super::super::parse_integer(res).map(#type_) },
+            );
+        }
+
+        match self {
+            ReturnType::Unit => (
+                quote! { () },
+                quote! { .error_for_status()?.text() },
+                quote! { super::super::parse_unit(#method_url, res) },
+            ),
+            ReturnType::Version => (
+                quote! { super::super::Version },
+                quote! { .error_for_status()?.text() },
+                quote! { res.parse() },
+            ),
+            ReturnType::Integer => (
+                quote! { i64 },
+                quote! { .error_for_status()?.text() },
+                quote! { super::super::parse_integer(res) },
+            ),
+            ReturnType::Bool => (
+                quote! { bool },
+                quote! { .error_for_status()?.text() },
+                quote! { super::super::parse_bool(res) },
+            ),
+            ReturnType::Text | ReturnType::Json => (
+                quote! { String },
+                quote! { .error_for_status()?.text() },
+                quote! { Ok(res) },
+            ),
+        }
+    }
+}
+
+pub fn generate_plain_text_helpers() -> TokenStream {
+    quote! {
+        /// A version as returned by `app/version` (`v4.1.3`) or `app/webapiVersion` (`2.0`).
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct Version {
+            pub major: u64,
+            pub minor: u64,
+            pub patch: u64,
+        }
+
+        impl std::fmt::Display for Version {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+            }
+        }
+
+        impl std::str::FromStr for Version {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self> {
+                let error = || Error::UnexpectedResponse(s.to_string());
+                let trimmed = s.trim();
+                let parts = trimmed
+                    .strip_prefix('v')
+                    .unwrap_or(trimmed)
+                    .split('.')
+                    .map(|part| {
+                        // ignore pre-release suffixes, e.g. `4.4.0beta1`
+                        let digits = part
+                            .find(|c: char| !c.is_ascii_digit())
+                            .map(|end| &part[..end])
+                            .unwrap_or(part);
+                        digits.parse::<u64>().map_err(|_| error())
+                    })
+                    .collect::<Result<std::vec::Vec<_>>>()?;
+
+                match parts[..] {
+                    [major] => Ok(Version { major, minor: 0, patch: 0 }),
+                    [major, minor] => Ok(Version { major, minor, patch: 0 }),
+                    [major, minor, patch, ..] => Ok(Version { major, minor, patch }),
+                    [] => Err(error()),
+                }
+            }
+        }
+
+        /// Deserializes a JSON response while keeping track of where in the response an
+        /// error occurred in order to be able to tell which field does not match.
+        fn parse_json<T: serde::de::DeserializeOwned>(endpoint: &str, res: String) -> Result<T> {
+            let deserializer = &mut serde_json::Deserializer::from_str(&res);
+
+            serde_path_to_error::deserialize(deserializer).map_err(|err| {
+                let path = err.path().to_string();
+                let source = err.into_inner();
+
+                Error::Decode {
+                    endpoint: endpoint.to_string(),
+                    path,
+                    snippet: snippet(&res, source.line(), source.column()),
+                    source,
+                }
+            })
+        }
+
+        /// The characters surrounding the given position (1-based line and column).
+        fn snippet(res: &str, line: usize, column: usize) -> String {
+            const CONTEXT: usize = 40;
+
+            let line = res.lines().nth(line.saturating_sub(1)).unwrap_or_default();
+            line.chars()
+                .skip(column.saturating_sub(CONTEXT))
+                .take(2 * CONTEXT)
+                .collect()
+        }
+
+        /// qBittorrent responds with "Fails." when an operation could not be performed.
+        fn parse_unit(url: &str, res: String) -> Result<()> {
+            if res.trim() == "Fails." {
+                Err(Error::OperationFailed(url.to_string()))
+            } else {
+                Ok(())
+            }
+        }
+
+        fn parse_integer(res: String) -> Result<i64> {
+            match res.trim().parse() {
+                Ok(value) => Ok(value),
+                Err(_) => Err(Error::UnexpectedResponse(res)),
+            }
+        }
+
+        fn parse_bool(res: String) -> Result<bool> {
+            match res.trim() {
+                "1" => Ok(true),
+                "0" => Ok(false),
+                _ => Err(Error::UnexpectedResponse(res)),
+            }
+        }
+    }
+}
@@ -63,6 +63,21 @@ pub fn generate_skeleton(ident: &syn::Ident) -> proc_macro2::TokenStream {
             InvalidUsernameOrPassword,
             #[error("request error: {0}")]
             HttpError(#[from] reqwest::Error),
+            #[error("failed to serialize request: {0}")]
+            SerializationError(#[from] serde_json::Error),
+            #[error("operation failed: {0}")]
+            OperationFailed(String),
+            #[error("unexpected response: {0}")]
+            UnexpectedResponse(String),
+            #[error("failed to decode the response of {endpoint} at `{path}` near `{snippet}`: {source}")]
+            Decode {
+                endpoint: String,
+                /// Where in the response the error occurred, e.g. `[42].state`.
+                path: String,
+                /// The part of the response surrounding the error.
+                snippet: String,
+                source: serde_json::Error,
+            },
         }
 
         type Result<T> = std::result::Result<T, Error>;
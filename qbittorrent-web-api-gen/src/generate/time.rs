@@ -0,0 +1,133 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::types;
+
+/// qBittorrent reports an ETA of 100 days when the torrent will never finish.
+const MAX_ETA: i64 = 8_640_000;
+
+/// Integer fields which are documented as a point in time or an amount of seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeKind {
+    Timestamp,
+    Duration,
+    Eta,
+}
+
+impl TimeKind {
+    fn type_(&self) -> TokenStream {
+        match self {
+            TimeKind::Timestamp if cfg!(feature = "chrono") => {
+                quote! { chrono::DateTime<chrono::Utc> }
+            }
+            TimeKind::Timestamp => quote! { ::time::OffsetDateTime },
+            TimeKind::Duration | TimeKind::Eta => quote! { std::time::Duration },
+        }
+    }
+
+    fn deserialize_with(&self) -> &'static str {
+        match self {
+            TimeKind::Timestamp => "super::super::serde_time::deserialize_timestamp",
+            TimeKind::Duration => "super::super::serde_time::deserialize_duration",
+            TimeKind::Eta => "super::super::serde_time::deserialize_eta",
+        }
+    }
+}
+
+/// Time types are only generated when either the `chrono` or the `time` feature is enabled.
+/// `chrono` is used when both are enabled, e.g. by two dependents or `--all-features`.
+pub fn is_enabled() -> bool {
+    cfg!(feature = "chrono") || cfg!(feature = "time")
+}
+
+impl types::Type {
+    pub fn time_kind(&self) -> Option<TimeKind> {
+        let type_info = match self {
+            types::Type::Number(type_info) => type_info,
+            _ => return None,
+        };
+
+        let description = type_info.description.as_ref()?.to_lowercase();
+        if type_info.name == "eta" {
+            Some(TimeKind::Eta)
+        } else if description.contains("unix epoch") || description.contains("unix timestamp") {
+            Some(TimeKind::Timestamp)
+        } else if description.contains("(seconds)") {
+            Some(TimeKind::Duration)
+        } else {
+            None
+        }
+    }
+
+    /// Generates a field where sentinel values such as `-1` are deserialized as `None`.
+    pub fn generate_time_field(&self, time_kind: TimeKind) -> TokenStream {
+        let name_snake = self.name_snake();
+        let orig_name = self.get_type_info().name.clone();
+        let type_ = time_kind.type_();
+        let deserialize_with = time_kind.deserialize_with();
+
+        quote! {
+            #[serde(rename = #orig_name, default, deserialize_with = #deserialize_with)]
+            pub #name_snake: std::option::Option<#type_>
+        }
+    }
+}
+
+pub fn generate_serde_time() -> TokenStream {
+    if !is_enabled() {
+        return quote! {};
+    }
+
+    let timestamp_type = TimeKind::Timestamp.type_();
+    let from_secs = if cfg!(feature = "chrono") {
+        quote! { chrono::TimeZone::timestamp_opt(&chrono::Utc, secs, 0).single() }
+    } else {
+        quote! { ::time::OffsetDateTime::from_unix_timestamp(secs).ok() }
+    };
+
+    quote! {
+        mod serde_time {
+            /// Unix timestamps where values `<= 0` mean that the time is not known,
+            /// e.g. `completion_on` of a torrent which hasn't completed yet.
+            pub fn deserialize_timestamp<'de, D>(
+                deserializer: D,
+            ) -> std::result::Result<std::option::Option<#timestamp_type>, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let secs = <std::option::Option<i64> as serde::Deserialize>::deserialize(deserializer)?;
+
+                Ok(secs.filter(|secs| *secs > 0).and_then(|secs| #from_secs))
+            }
+
+            /// Seconds where negative values mean that the duration is not known or unlimited.
+            pub fn deserialize_duration<'de, D>(
+                deserializer: D,
+            ) -> std::result::Result<std::option::Option<std::time::Duration>, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let secs = <std::option::Option<i64> as serde::Deserialize>::deserialize(deserializer)?;
+
+                Ok(secs
+                    .and_then(|secs| u64::try_from(secs).ok())
+                    .map(std::time::Duration::from_secs))
+            }
+
+            /// Same as [`deserialize_duration`] but an ETA of `8640000` means "infinity".
+            pub fn deserialize_eta<'de, D>(
+                deserializer: D,
+            ) -> std::result::Result<std::option::Option<std::time::Duration>, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let secs = <std::option::Option<i64> as serde::Deserialize>::deserialize(deserializer)?;
+
+                Ok(secs
+                    .filter(|secs| *secs != #MAX_ETA)
+                    .and_then(|secs| u64::try_from(secs).ok())
+                    .map(std::time::Duration::from_secs))
+            }
+        }
+    }
+}
@@ -1,10 +1,23 @@
 use quote::quote;
 use quote::ToTokens;
 
+use crate::md_parser;
+
 pub fn to_ident(name: &str) -> proc_macro2::Ident {
     syn::Ident::new(name, proc_macro2::Span::call_site())
 }
 
+/// Appends where the item is documented in the api markdown, e.g. "Documented at line 12 of
+/// `api-4_1.md`.", to its docs.
+pub fn with_source(docs: &Option<String>, span: Option<md_parser::Span>) -> Option<String> {
+    let source = span.map(|span| format!("Documented at line {} of `api-4_1.md`.", span.line));
+
+    match (docs, source) {
+        (Some(docs), Some(source)) => Some(format!("{docs}\n\n{source}")),
+        (docs, source) => docs.clone().or(source),
+    }
+}
+
 pub fn add_docs<T: ToTokens>(docs: &Option<String>, stream: T) -> proc_macro2::TokenStream {
     if let Some(docs) = docs {
         quote! {
@@ -1,5 +1,15 @@
+/// A regex which is compiled once, on first use, instead of on every call.
+macro_rules! regex {
+    ($re:literal) => {{
+        static RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        RE.get_or_init(|| regex::Regex::new($re).expect("Failed to create regex"))
+    }};
+}
+
+mod error;
 mod generate;
 mod md_parser;
+mod openapi;
 mod parser;
 mod types;
 
@@ -11,5 +21,12 @@ const API_CONTENT: &str = include_str!("../api-4_1.md");
 #[proc_macro_derive(QBittorrentApiGen, attributes(api_gen))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as syn::DeriveInput);
-    generate::generate(&ast, API_CONTENT).into()
+
+    match generate::generate(&ast, API_CONTENT) {
+        Ok(tokens) => tokens.into(),
+        // reported as a `compile_error!` on the derived struct instead of a panic
+        Err(err) => syn::Error::new(ast.ident.span(), err)
+            .to_compile_error()
+            .into(),
+    }
 }
@@ -0,0 +1,276 @@
+use std::ops::Range;
+
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag};
+
+use super::md_token::{
+    CodeBlock, Header, ListItem, MdContent, MdToken, Span, Spanned, Table, TableRow,
+};
+
+/// Tokenizes the markdown with a CommonMark parser (with GitHub flavored tables) instead of
+/// line by line. It understands the layout of the upstream wiki page as well, e.g. setext
+/// headers, escaped pipes in tables and tables with leading pipes, while producing the same
+/// tokens as `MdToken::from` for the markdown which that one understands.
+pub fn tokens(content: &str) -> Vec<MdToken> {
+    let source = Source::new(content);
+
+    let mut tokens = Vec::new();
+    let mut next_line = 0;
+    for (range, block) in blocks(content) {
+        let (start, end) = source.lines_of(&range);
+        // e.g. multiple html events on the same line
+        if start < next_line {
+            continue;
+        }
+
+        // the parser doesn't emit events for blank lines between blocks
+        tokens.extend((next_line..start).map(|index| source.text(index)));
+        tokens.extend(block.into_tokens(&source, start, end));
+        next_line = end + 1;
+    }
+    tokens.extend((next_line..source.lines.len()).map(|index| source.text(index)));
+
+    tokens
+}
+
+/// The top level blocks of the markdown together with their byte ranges.
+fn blocks(content: &str) -> Vec<(Range<usize>, Block)> {
+    let mut blocks: Vec<(Range<usize>, Block)> = Vec::new();
+    let mut depth = 0;
+
+    for (event, range) in Parser::new_ext(content, Options::ENABLE_TABLES).into_offset_iter() {
+        match event {
+            Event::Start(tag) => {
+                match blocks.last_mut() {
+                    Some((_, block)) if depth > 0 => block.start_nested(&tag, range),
+                    _ => blocks.push((range, Block::new(tag))),
+                }
+                depth += 1;
+            }
+            Event::End(_) => depth -= 1,
+            Event::Text(text) if depth > 0 => {
+                if let Some((_, Block::Code { code, .. })) = blocks.last_mut() {
+                    code.push_str(&text);
+                }
+            }
+            // e.g. html blocks and rules
+            _ if depth == 0 => blocks.push((range, Block::Other)),
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+enum Block {
+    Heading(i32),
+    Paragraph,
+    Code {
+        language: Option<String>,
+        code: String,
+    },
+    /// The start of each list item, including nested ones.
+    List(Vec<usize>),
+    /// The cells of the header followed by the cells of each row.
+    Table(Vec<Vec<Range<usize>>>),
+    Other,
+}
+
+impl Block {
+    fn new(tag: Tag) -> Self {
+        match tag {
+            Tag::Heading(level, ..) => Block::Heading(heading_level(level)),
+            Tag::Paragraph => Block::Paragraph,
+            Tag::CodeBlock(kind) => Block::Code {
+                language: match kind {
+                    CodeBlockKind::Fenced(language) if !language.trim().is_empty() => {
+                        Some(language.trim().to_string())
+                    }
+                    _ => None,
+                },
+                code: String::new(),
+            },
+            Tag::List(_) => Block::List(Vec::new()),
+            Tag::Table(_) => Block::Table(Vec::new()),
+            _ => Block::Other,
+        }
+    }
+
+    fn start_nested(&mut self, tag: &Tag, range: Range<usize>) {
+        match (self, tag) {
+            (Block::List(items), Tag::Item) => items.push(range.start),
+            (Block::Table(rows), Tag::TableHead | Tag::TableRow) => rows.push(Vec::new()),
+            (Block::Table(rows), Tag::TableCell) => {
+                if let Some(row) = rows.last_mut() {
+                    row.push(range);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn into_tokens(self, source: &Source, start: usize, end: usize) -> Vec<MdToken> {
+        let line = source.lines[start];
+
+        match self {
+            Block::Heading(level) => vec![MdToken::Header(Header {
+                level,
+                content: line.trim().trim_matches('#').trim().to_string(),
+                // setext headers are underlined on the next line
+                span: Span {
+                    end_line: end + 1,
+                    ..Span::single_line(start, line, '#')
+                },
+            })],
+            // e.g. "***" rules are asterisk lines as well
+            Block::Paragraph | Block::Other => (start..=end)
+                .map(|index| source.paragraph_line(index))
+                .collect(),
+            Block::Code { language, mut code } => {
+                if code.ends_with('\n') {
+                    code.pop();
+                }
+
+                vec![source.content(
+                    start,
+                    end,
+                    MdContent::CodeBlock(CodeBlock {
+                        language,
+                        code,
+                        raw: source.lines[start..=end].join("\n"),
+                    }),
+                )]
+            }
+            Block::List(items) => {
+                let item_lines: Vec<usize> =
+                    items.iter().map(|item| source.line_of(*item)).collect();
+
+                (start..=end)
+                    .map(|index| {
+                        if item_lines.contains(&index) {
+                            let list_item = list_item(source.lines[index]);
+                            source.content(index, index, MdContent::ListItem(list_item))
+                        } else {
+                            // e.g. the continuation of an item or a blank line between items
+                            source.text(index)
+                        }
+                    })
+                    .collect()
+            }
+            Block::Table(rows) => vec![source.content(start, end, source.table(start, &rows))],
+        }
+    }
+}
+
+fn heading_level(level: HeadingLevel) -> i32 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+/// e.g. "   1. [Login](#login)" or "* item", the parser has already checked that it's an item.
+fn list_item(line: &str) -> ListItem {
+    let item = line.trim_start_matches(' ');
+    let (marker, text) = item.split_once(' ').unwrap_or((item, ""));
+
+    ListItem {
+        ordered: marker.ends_with('.') || marker.ends_with(')'),
+        indent: line.len() - item.len(),
+        text: text.trim_start().to_string(),
+        raw: line.to_string(),
+    }
+}
+
+struct Source<'a> {
+    content: &'a str,
+    lines: Vec<&'a str>,
+    /// The byte offset of the start of each line.
+    line_starts: Vec<usize>,
+}
+
+impl<'a> Source<'a> {
+    fn new(content: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        Source {
+            content,
+            lines: content.lines().collect(),
+            line_starts,
+        }
+    }
+
+    fn line_of(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= offset) - 1
+    }
+
+    /// The first and last line of a range, which usually ends with the newline of its last line.
+    fn lines_of(&self, range: &Range<usize>) -> (usize, usize) {
+        let last = range.end.saturating_sub(1).max(range.start);
+        (self.line_of(range.start), self.line_of(last))
+    }
+
+    fn content(&self, start: usize, end: usize, content: MdContent) -> MdToken {
+        MdToken::Content(Spanned {
+            value: content,
+            span: Span {
+                end_line: end + 1,
+                ..Span::single_line(start, self.lines[start], ' ')
+            },
+        })
+    }
+
+    fn text(&self, index: usize) -> MdToken {
+        let line = self.lines[index];
+        self.content(index, index, MdContent::Text(line.to_string()))
+    }
+
+    /// Lines starting with `*`, e.g. the bold "**Returns:**", are asterisk lines, just like
+    /// `MdToken::from` does.
+    fn paragraph_line(&self, index: usize) -> MdToken {
+        let line = self.lines[index];
+        if !line.starts_with('*') {
+            return self.text(index);
+        }
+
+        MdToken::Content(Spanned {
+            value: MdContent::Asterisk(line.trim_matches('*').trim().to_string()),
+            span: Span::single_line(index, line, '*'),
+        })
+    }
+
+    /// A table starting at the line `start` with the header followed by the separator row.
+    fn table(&self, start: usize, rows: &[Vec<Range<usize>>]) -> MdContent {
+        let mut rows = rows.iter().filter_map(|cells| {
+            let line = self.line_of(cells.first()?.start);
+            Some(TableRow {
+                raw: self.lines[line].to_string(),
+                columns: cells.iter().map(|cell| self.column(cell)).collect(),
+            })
+        });
+
+        MdContent::Table(Table {
+            header: rows.next().unwrap_or_else(|| TableRow {
+                raw: self.lines[start].to_string(),
+                columns: Vec::new(),
+            }),
+            split: self.lines.get(start + 1).unwrap_or(&"").to_string(),
+            rows: rows.collect(),
+        })
+    }
+
+    /// The raw markdown of a cell, e.g. links are kept as is, with the same cleanup as
+    /// `MdToken::from` does.
+    fn column(&self, cell: &Range<usize>) -> String {
+        self.content[cell.clone()]
+            .replace('`', "")
+            .replace("\\|", "|")
+            .trim()
+            .to_string()
+    }
+}
@@ -0,0 +1,93 @@
+use super::MdContent;
+
+/// The parts of a line of text, e.g. "see [Get torrent list](#get-torrent-list)" is the text
+/// "see " followed by a link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline {
+    Text(String),
+    /// e.g. `hashes`, without the backticks.
+    Code(String),
+    Link(Link),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub text: String,
+    /// e.g. `#get-torrent-list` or `https://github.com/qbittorrent/qBittorrent/pull/8782`.
+    pub target: String,
+}
+
+impl Link {
+    /// The anchor of a link within the document, e.g. `get-torrent-list`.
+    pub fn anchor(&self) -> Option<&str> {
+        self.target.strip_prefix('#')
+    }
+}
+
+impl MdContent {
+    /// The inline code and links of text, asterisk lines and list items. Other content has
+    /// no inline elements.
+    pub fn inlines(&self) -> Vec<Inline> {
+        match self {
+            MdContent::Text(text) | MdContent::Asterisk(text) => parse_inlines(text),
+            MdContent::ListItem(list_item) => parse_inlines(&list_item.text),
+            MdContent::Table(_) | MdContent::CodeBlock(_) => vec![],
+        }
+    }
+}
+
+fn parse_inlines(text: &str) -> Vec<Inline> {
+    let re = regex!(r"`([^`]*)`|\[([^\]]*)\]\(([^)]*)\)");
+
+    let mut inlines = vec![];
+    let mut end_of_previous = 0;
+    for captures in re.captures_iter(text) {
+        let whole = captures.get(0).expect("The whole match is always present");
+        if whole.start() > end_of_previous {
+            inlines.push(Inline::Text(
+                text[end_of_previous..whole.start()].to_string(),
+            ));
+        }
+
+        inlines.push(match (captures.get(1), captures.get(2), captures.get(3)) {
+            (Some(code), ..) => Inline::Code(code.as_str().to_string()),
+            (None, Some(link_text), Some(target)) => Inline::Link(Link {
+                text: link_text.as_str().to_string(),
+                target: target.as_str().to_string(),
+            }),
+            _ => unreachable!("Either inline code or a link is matched"),
+        });
+        end_of_previous = whole.end();
+    }
+
+    if end_of_previous < text.len() {
+        inlines.push(Inline::Text(text[end_of_previous..].to_string()));
+    }
+
+    inlines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_with_code_and_link() {
+        let content = MdContent::Text(
+            "Set `id` to the priority, see [Get torrent contents](#get-torrent-contents)".into(),
+        );
+
+        assert_eq!(
+            content.inlines(),
+            vec![
+                Inline::Text("Set ".into()),
+                Inline::Code("id".into()),
+                Inline::Text(" to the priority, see ".into()),
+                Inline::Link(Link {
+                    text: "Get torrent contents".into(),
+                    target: "#get-torrent-contents".into(),
+                }),
+            ]
+        );
+    }
+}
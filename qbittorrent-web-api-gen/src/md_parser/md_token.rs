@@ -1,8 +1,70 @@
+use crate::error::{ParseError, ParseResult};
+
+type Lines<'a> = std::iter::Peekable<std::iter::Enumerate<std::str::Lines<'a>>>;
+
+/// Where a token is located in the markdown. Lines and columns are 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    /// The column where the content starts, i.e. after the `#` of a header.
+    pub column: usize,
+    /// The last line of the token, e.g. the last row of a table.
+    pub end_line: usize,
+}
+
+impl Span {
+    /// A single line whose content starts after any leading `prefix` and whitespace.
+    pub(super) fn single_line(index: usize, line: &str, prefix: char) -> Self {
+        let content = line.trim_start_matches(prefix).trim_start();
+
+        Span {
+            line: index + 1,
+            column: line.chars().count() - content.chars().count() + 1,
+            end_line: index + 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Span,
+}
+
+impl<T> std::ops::Deref for Spanned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MdContent {
     Text(String),
     Asterisk(String),
     Table(Table),
+    /// A fenced code block, e.g. the ```` ```http ```` example of a server reply.
+    CodeBlock(CodeBlock),
+    /// e.g. "- otherwise, a JSON object with the following fields" or "1. Main version".
+    ListItem(ListItem),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// e.g. `JSON` or `http`.
+    pub language: Option<String>,
+    pub code: String,
+    pub(super) raw: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListItem {
+    pub ordered: bool,
+    /// The number of spaces before the marker, i.e. how deeply the item is nested.
+    pub indent: usize,
+    pub text: String,
+    pub(super) raw: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -27,11 +89,12 @@ impl Table {
 pub struct Header {
     pub level: i32,
     pub content: String,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TableRow {
-    raw: String,
+    pub(super) raw: String,
     pub columns: Vec<String>,
 }
 
@@ -41,6 +104,8 @@ impl MdContent {
             MdContent::Text(text) => text.into(),
             MdContent::Asterisk(text) => text.into(),
             MdContent::Table(table) => table.raw(),
+            MdContent::CodeBlock(code_block) => code_block.raw.clone(),
+            MdContent::ListItem(list_item) => list_item.raw.clone(),
         }
     }
 }
@@ -48,38 +113,130 @@ impl MdContent {
 #[derive(Debug)]
 pub enum MdToken {
     Header(Header),
-    Content(MdContent),
+    Content(Spanned<MdContent>),
 }
 
 impl MdToken {
-    pub fn from(content: &str) -> Vec<MdToken> {
+    pub fn from(content: &str) -> ParseResult<Vec<MdToken>> {
         // to prevent infinite loops
         let mut max_iterator_checker = MaxIteratorChecker::default();
 
         let mut output = Vec::new();
-        let mut iter = content.lines().peekable();
+        let mut iter = content.lines().enumerate().peekable();
 
-        while let Some(line) = iter.next() {
-            max_iterator_checker.decrease();
+        while let Some((index, line)) = iter.next() {
+            max_iterator_checker.decrease(index)?;
 
-            if line.contains(" | ") || line.contains("-|") || line.contains("|-") {
-                let table = TableParser::new(&mut max_iterator_checker, &mut iter).parse(line);
+            // checked first since code blocks may contain anything, including `|` and `#`
+            if let Some(language) = line.trim_start().strip_prefix(FENCE) {
+                let code_block = parse_code_block(
+                    &mut max_iterator_checker,
+                    &mut iter,
+                    (index, line),
+                    language,
+                )?;
+                output.push(MdToken::Content(code_block));
+            } else if let Some(list_item) = parse_list_item(line) {
+                output.push(MdToken::Content(Spanned {
+                    value: MdContent::ListItem(list_item),
+                    span: Span::single_line(index, line, ' '),
+                }));
+            } else if line.contains(" | ")
+                || line.contains("-|")
+                || line.contains("|-")
+                // e.g. the single column "Value |" followed by "------|"
+                || (line.contains('|') && iter.peek().is_some_and(|(_, next)| is_separator(next)))
+            {
+                let table =
+                    TableParser::new(&mut max_iterator_checker, &mut iter).parse(index, line)?;
                 output.push(MdToken::Content(table));
             } else if line.starts_with('#') {
-                output.push(parse_header(line));
+                output.push(parse_header(index, line));
             } else if line.starts_with('*') {
-                let asterisk = MdContent::Asterisk(line.trim_matches('*').trim().to_string());
-                output.push(MdToken::Content(asterisk));
+                output.push(MdToken::Content(Spanned {
+                    value: MdContent::Asterisk(line.trim_matches('*').trim().to_string()),
+                    span: Span::single_line(index, line, '*'),
+                }));
             } else {
-                output.push(MdToken::Content(MdContent::Text(line.to_string())));
+                output.push(MdToken::Content(Spanned {
+                    value: MdContent::Text(line.to_string()),
+                    span: Span::single_line(index, line, ' '),
+                }));
             }
         }
 
-        output
+        Ok(output)
     }
 }
 
-fn parse_header(line: &str) -> MdToken {
+const FENCE: &str = "```";
+
+fn parse_code_block(
+    max_iterator_checker: &mut MaxIteratorChecker,
+    iter: &mut Lines,
+    (index, line): (usize, &str),
+    language: &str,
+) -> ParseResult<Spanned<MdContent>> {
+    let mut raw = vec![line];
+    let mut code = vec![];
+
+    loop {
+        let (end_index, code_line) = iter.next().ok_or_else(|| {
+            ParseError::new(format!("a closing {FENCE} for the code block")).at_line(index + 1)
+        })?;
+        max_iterator_checker.decrease(end_index)?;
+        raw.push(code_line);
+
+        if code_line.trim() == FENCE {
+            let language = language.trim();
+
+            return Ok(Spanned {
+                value: MdContent::CodeBlock(CodeBlock {
+                    language: (!language.is_empty()).then(|| language.to_string()),
+                    code: code.join("\n"),
+                    raw: raw.join("\n"),
+                }),
+                span: Span {
+                    end_line: end_index + 1,
+                    ..Span::single_line(index, line, ' ')
+                },
+            });
+        }
+
+        code.push(code_line);
+    }
+}
+
+/// e.g. "- otherwise, ...", "* item" or "   1. [Login](#login)". Only a lone `*` is a marker,
+/// so a bold "**Returns:**" is still parsed as an asterisk line.
+fn parse_list_item(line: &str) -> Option<ListItem> {
+    let item = line.trim_start_matches(' ');
+    let (marker, text) = item.split_once(' ')?;
+
+    let ordered = match marker.strip_suffix('.') {
+        Some(number) if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) => true,
+        _ if marker == "-" || marker == "+" || marker == "*" => false,
+        _ => return None,
+    };
+
+    Some(ListItem {
+        ordered,
+        indent: line.len() - item.len(),
+        text: text.trim_start().to_string(),
+        raw: line.to_string(),
+    })
+}
+
+/// e.g. "---|---" or "|:---|"
+fn is_separator(line: &str) -> bool {
+    line.contains('-')
+        && line.contains('|')
+        && line
+            .chars()
+            .all(|c| c == '-' || c == '|' || c == ':' || c == ' ')
+}
+
+fn parse_header(index: usize, line: &str) -> MdToken {
     let mut level = 0;
     for char in line.chars() {
         if char != '#' {
@@ -91,68 +248,90 @@ fn parse_header(line: &str) -> MdToken {
     MdToken::Header(Header {
         level,
         content: line.trim_matches('#').trim().to_string(),
+        span: Span::single_line(index, line, '#'),
     })
 }
 
 struct TableParser<'a, 'b> {
     max_iterator_checker: &'a mut MaxIteratorChecker,
-    iter: &'a mut std::iter::Peekable<std::str::Lines<'b>>,
+    iter: &'a mut Lines<'b>,
 }
 
 impl<'a, 'b> TableParser<'a, 'b> {
-    fn new(
-        max_iterator_checker: &'a mut MaxIteratorChecker,
-        iter: &'a mut std::iter::Peekable<std::str::Lines<'b>>,
-    ) -> Self {
+    fn new(max_iterator_checker: &'a mut MaxIteratorChecker, iter: &'a mut Lines<'b>) -> Self {
         Self {
             max_iterator_checker,
             iter,
         }
     }
 
-    fn parse(&mut self, line: &str) -> MdContent {
+    fn parse(&mut self, index: usize, line: &str) -> ParseResult<Spanned<MdContent>> {
         let table_header = TableRow {
             raw: line.into(),
             columns: Self::to_columns(line),
         };
 
-        let table_split = self.iter.next().unwrap();
-        let table_rows = self.table_rows();
+        let (split_index, table_split) = self.iter.next().ok_or_else(|| {
+            ParseError::new("a separator row, e.g. `---|---`, after the table header")
+                .at_line(index + 1)
+        })?;
+        let mut span = Span::single_line(index, line, ' ');
+        span.end_line = split_index + 1;
+        let table_rows = self.table_rows(&mut span)?;
 
-        MdContent::Table(Table {
-            header: table_header,
-            split: table_split.to_string(),
-            rows: table_rows,
+        Ok(Spanned {
+            value: MdContent::Table(Table {
+                header: table_header,
+                split: table_split.to_string(),
+                rows: table_rows,
+            }),
+            span,
         })
     }
 
-    fn table_rows(&mut self) -> Vec<TableRow> {
+    fn table_rows(&mut self, span: &mut Span) -> ParseResult<Vec<TableRow>> {
         let mut table_rows = Vec::new();
 
-        while let Some(peeked_row_line) = self.iter.peek() {
-            self.max_iterator_checker.decrease();
+        // we've reached the end of the table once a line doesn't contain a column separator
+        while let Some((index, next_row_line)) = self.iter.next_if(|(_, line)| line.contains('|')) {
+            self.max_iterator_checker.decrease(index)?;
 
-            if !peeked_row_line.contains('|') {
-                // we've reached the end of the table, let's go back one step
-                break;
-            }
-
-            let next_row_line = self.iter.next().unwrap();
             table_rows.push(TableRow {
                 raw: next_row_line.to_string(),
                 columns: Self::to_columns(next_row_line),
             });
+            span.end_line = index + 1;
         }
 
-        table_rows
+        Ok(table_rows)
     }
 
+    /// Splits a row at its unescaped pipes, e.g. "`hashes` | separated by `\|`" has the
+    /// columns "hashes" and "separated by |". The leading and trailing pipe of rows like
+    /// "`connected` |" are optional.
     fn to_columns(column_line: &str) -> Vec<String> {
-        column_line
-            .replace('`', "")
-            .split('|')
-            .map(|s| s.trim().to_string())
-            .collect()
+        let trimmed = column_line.trim();
+        let trimmed = trimmed.strip_prefix('|').unwrap_or(trimmed);
+        let trimmed = match trimmed.strip_suffix('|') {
+            Some(row) if !row.ends_with('\\') => row,
+            _ => trimmed,
+        };
+
+        let mut columns = vec![String::new()];
+        let mut chars = trimmed.chars().peekable();
+        while let Some(char) = chars.next() {
+            match char {
+                '\\' if chars.peek() == Some(&'|') => {
+                    columns.last_mut().unwrap().push('|');
+                    chars.next();
+                }
+                '|' => columns.push(String::new()),
+                '`' => {}
+                _ => columns.last_mut().unwrap().push(char),
+            }
+        }
+
+        columns.iter().map(|s| s.trim().to_string()).collect()
     }
 }
 
@@ -162,11 +341,16 @@ struct MaxIteratorChecker {
 }
 
 impl MaxIteratorChecker {
-    fn decrease(&mut self) {
+    fn decrease(&mut self, index: usize) -> ParseResult<()> {
         self.max_iterations -= 1;
         if self.max_iterations <= 0 {
-            panic!("Max iterations reached, missing termination?");
+            return Err(ParseError::new(
+                "the markdown to end within the maximum number of iterations",
+            )
+            .at_line(index + 1));
         }
+
+        Ok(())
     }
 }
 
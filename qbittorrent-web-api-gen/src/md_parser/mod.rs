@@ -1,7 +1,11 @@
+#[cfg(feature = "commonmark")]
+mod commonmark;
+mod inline;
 mod md_token;
 mod token_tree;
 mod token_tree_factory;
 
+pub use inline::*;
 pub use md_token::*;
 pub use token_tree::TokenTree;
 pub use token_tree_factory::TokenTreeFactory;
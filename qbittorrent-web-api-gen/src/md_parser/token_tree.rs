@@ -1,14 +1,37 @@
 use std::rc::Rc;
 
-use super::{md_token::MdContent, token_tree_factory::TokenTreeFactory};
+use super::{
+    md_token::{MdContent, Span, Spanned},
+    token_tree_factory::TokenTreeFactory,
+};
+use crate::error::ParseError;
 
 #[derive(Debug)]
 pub struct TokenTree {
     pub title: Option<String>,
-    pub content: Vec<MdContent>,
+    /// The span of the header, `None` for the root of the document.
+    pub span: Option<Span>,
+    pub content: Vec<Spanned<MdContent>>,
     pub children: Vec<TokenTree>,
 }
 
+impl TokenTree {
+    /// An error in the section of this tree.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.locate(ParseError::new(expected))
+    }
+
+    /// Points an error raised while parsing this section at its header, unless it has been
+    /// located in a more specific section already.
+    pub fn locate(&self, mut err: ParseError) -> ParseError {
+        if err.section.is_none() && err.line.is_none() {
+            err.line = self.span.map(|span| span.line);
+        }
+
+        err.in_section(self.title.as_deref())
+    }
+}
+
 impl From<Rc<TokenTreeFactory>> for TokenTree {
     fn from(builder: Rc<TokenTreeFactory>) -> Self {
         let children = builder
@@ -23,6 +46,7 @@ impl From<Rc<TokenTreeFactory>> for TokenTree {
 
         TokenTree {
             title: builder.title.clone(),
+            span: builder.span,
             content,
             children,
         }
@@ -1,32 +1,37 @@
 use std::{cell::RefCell, rc::Rc};
 
+#[cfg(feature = "commonmark")]
+use super::commonmark;
 use super::{
-    md_token::{Header, MdContent, MdToken},
+    md_token::{Header, MdContent, MdToken, Span, Spanned},
     token_tree::TokenTree,
 };
+use crate::error::ParseResult;
 
 #[derive(Debug, Default)]
 pub struct TokenTreeFactory {
     pub title: Option<String>,
-    pub content: RefCell<Vec<MdContent>>,
+    pub span: Option<Span>,
+    pub content: RefCell<Vec<Spanned<MdContent>>>,
     pub children: RefCell<Vec<Rc<TokenTreeFactory>>>,
     pub level: i32,
 }
 
 impl TokenTreeFactory {
-    fn new(title: &str, level: i32) -> Self {
+    fn new(title: &str, level: i32, span: Span) -> Self {
         Self {
             title: if title.is_empty() {
                 None
             } else {
                 Some(title.to_string())
             },
+            span: Some(span),
             level,
             ..Default::default()
         }
     }
 
-    fn add_content(&self, content: MdContent) {
+    fn add_content(&self, content: Spanned<MdContent>) {
         self.content.borrow_mut().push(content);
     }
 
@@ -34,17 +39,31 @@ impl TokenTreeFactory {
         self.children.borrow_mut().push(child.clone());
     }
 
-    pub fn create(content: &str) -> TokenTree {
-        let tokens = MdToken::from(content);
+    // unused by `generate` with the `commonmark` feature, but still compared to it in the tests
+    #[cfg_attr(feature = "commonmark", allow(dead_code))]
+    pub fn create(content: &str) -> ParseResult<TokenTree> {
+        Ok(Self::from_tokens(MdToken::from(content)?))
+    }
+
+    /// Same as `create` but parses the markdown with a CommonMark parser, see `commonmark::tokens`.
+    #[cfg(feature = "commonmark")]
+    pub fn create_commonmark(content: &str) -> ParseResult<TokenTree> {
+        Ok(Self::from_tokens(commonmark::tokens(content)))
+    }
 
+    fn from_tokens(tokens: Vec<MdToken>) -> TokenTree {
         let mut stack = Vec::new();
         let root = Rc::new(TokenTreeFactory::default());
         stack.push(root.clone());
 
         for token in tokens {
             match token {
-                MdToken::Header(Header { level, content }) => {
-                    let new_header = Rc::new(TokenTreeFactory::new(&content, level));
+                MdToken::Header(Header {
+                    level,
+                    content,
+                    span,
+                }) => {
+                    let new_header = Rc::new(TokenTreeFactory::new(&content, level, span));
 
                     // go back until we're at the same or lower level.
                     while let Some(current) = stack.pop() {
@@ -58,9 +77,10 @@ impl TokenTreeFactory {
                     stack.push(new_header.clone());
                 }
                 MdToken::Content(content) => {
-                    let current = stack.pop().unwrap();
-                    current.add_content(content);
-                    stack.push(current);
+                    // the root is never removed from the stack
+                    if let Some(current) = stack.last() {
+                        current.add_content(content);
+                    }
                 }
             }
         }
@@ -87,12 +107,18 @@ mod tests {
             let input = include_str!(concat!(TEST_DIR!(), "/", $test_file, ".md"));
 
             // when
-            let tree = TokenTreeFactory::create(input);
+            let tree = TokenTreeFactory::create(input).unwrap();
 
             // then
             let tree_as_str = format!("{tree:#?}");
             let should_be = include_str!(concat!(TEST_DIR!(), "/", $test_file, ".check"));
             assert_eq!(tree_as_str, should_be);
+
+            // and both backends agree
+            #[cfg(feature = "commonmark")]
+            let commonmark_tree = TokenTreeFactory::create_commonmark(input).unwrap();
+            #[cfg(feature = "commonmark")]
+            assert_eq!(format!("{commonmark_tree:#?}"), should_be);
         };
     }
 
@@ -104,7 +130,7 @@ mod tests {
             use std::path::Path;
 
             let input = include_str!(concat!(TEST_DIR!(), "/", $test_file, ".md"));
-            let tree = TokenTreeFactory::create(input);
+            let tree = TokenTreeFactory::create(input).unwrap();
             let tree_as_str = format!("{tree:#?}");
             let file = concat!("src/md_parser/", TEST_DIR!(), "/", $test_file, ".check");
 
@@ -151,4 +177,64 @@ mod tests {
     fn non_table_with_pipe() {
         run_test!("non_table_with_pipe");
     }
+
+    #[test]
+    fn code_block_and_list() {
+        run_test!("code_block_and_list");
+    }
+
+    #[test]
+    #[cfg(feature = "commonmark")]
+    fn commonmark_understands_the_upstream_layout() {
+        let content =
+            "Foo\n===\n\n| Name | Description |\n|---|---|\n| `hashes` | separated by `\\|` |";
+
+        let tree = TokenTreeFactory::create_commonmark(content).unwrap();
+
+        let foo = &tree.children[0];
+        assert_eq!(foo.title.as_deref(), Some("Foo"));
+        match &foo.content[1].value {
+            MdContent::Table(table) => {
+                assert_eq!(table.header.columns, vec!["Name", "Description"]);
+                assert_eq!(table.rows[0].columns, vec!["hashes", "separated by |"]);
+            }
+            content => panic!("Expected a table, found: {content:?}"),
+        }
+    }
+
+    #[test]
+    fn escaped_pipes_and_single_column_tables() {
+        let content = "# Foo #\n\nName | Description\n---|---\n`hashes` | separated by `\\|`\n\nValue |\n------|\n`connected` |";
+
+        assert_escaped_pipes_and_single_column_tables(TokenTreeFactory::create(content).unwrap());
+        #[cfg(feature = "commonmark")]
+        assert_escaped_pipes_and_single_column_tables(
+            TokenTreeFactory::create_commonmark(content).unwrap(),
+        );
+    }
+
+    fn assert_escaped_pipes_and_single_column_tables(tree: TokenTree) {
+        let tables: Vec<_> = tree.children[0]
+            .content
+            .iter()
+            .filter_map(|row| match &row.value {
+                MdContent::Table(table) => Some(table),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(tables.len(), 2, "{tree:#?}");
+        assert_eq!(tables[0].rows[0].columns, vec!["hashes", "separated by |"]);
+        assert_eq!(tables[1].header.columns, vec!["Value"]);
+        assert_eq!(tables[1].rows[0].columns, vec!["connected"]);
+    }
+
+    #[test]
+    fn table_without_separator_is_an_error() {
+        let content = "# Title #\n\nName | Type | Description";
+
+        let err = TokenTreeFactory::create(content).unwrap_err();
+
+        assert_eq!(err.line, Some(3));
+    }
 }
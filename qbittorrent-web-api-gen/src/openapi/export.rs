@@ -0,0 +1,364 @@
+use serde_json::{json, Map, Value};
+
+use crate::{
+    parser::{self, ApiGroup, ApiMethod, CompositeType, ReturnType},
+    types::{self, RefType},
+};
+
+use super::SCHEMAS;
+
+/// The parsed API as an OpenAPI 3 document, e.g. to be used with standard tooling or to
+/// review the parsed model as data.
+pub fn document(groups: &[ApiGroup], version: &str) -> Value {
+    let mut paths = Map::new();
+    let mut schemas = Map::new();
+
+    for group in groups {
+        for method in &group.methods {
+            let path = format!("/api/v2/{}/{}", group.url, method.url);
+            paths.insert(path, json!({ "post": operation(group, method) }));
+            add_schemas(&mut schemas, method);
+        }
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "qBittorrent Web API",
+            "version": version,
+        },
+        "tags": groups
+            .iter()
+            .map(|group| with_description(json!({ "name": group.name }), &group.description))
+            .collect::<Vec<_>>(),
+        "paths": paths,
+        "components": {
+            "schemas": schemas,
+            "securitySchemes": {
+                "SID": { "type": "apiKey", "in": "cookie", "name": "SID" },
+            },
+        },
+        "security": [{ "SID": [] }],
+    })
+}
+
+fn operation(group: &ApiGroup, method: &ApiMethod) -> Value {
+    let mut operation = json!({
+        "operationId": format!("{}/{}", group.url, method.url),
+        "tags": [group.name],
+        "responses": { "200": response(method) },
+    });
+
+    if let Some(request_body) = request_body(method) {
+        operation["requestBody"] = request_body;
+    }
+
+    with_description(operation, &method.description)
+}
+
+/// Parameters are sent as a multipart form, e.g. `hashes=...&category=...`.
+fn request_body(method: &ApiMethod) -> Option<Value> {
+    let parameters = method.types.parameters();
+    let json_parameter = method.types.json_parameter();
+    if parameters.is_empty() && json_parameter.is_none() {
+        return None;
+    }
+
+    let mut properties = Map::new();
+    let mut encoding = Map::new();
+    for parameter in &parameters {
+        let type_info = parameter.get_type_info();
+        properties.insert(type_info.name.clone(), field_schema(parameter));
+    }
+    // e.g. `setPreferences` which takes its fields json encoded in a single parameter
+    if let Some(json_parameter) = json_parameter {
+        properties.insert("json".into(), reference(&json_parameter.name));
+        encoding.insert("json".into(), json!({ "contentType": "application/json" }));
+    }
+
+    let required: Vec<&str> = method
+        .types
+        .mandatory_params()
+        .iter()
+        .map(|parameter| parameter.get_type_info().name.as_str())
+        .chain(json_parameter.map(|_| "json"))
+        .collect();
+
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+
+    Some(json!({
+        "required": !required.is_empty(),
+        "content": {
+            "multipart/form-data": { "schema": schema, "encoding": encoding },
+        },
+    }))
+}
+
+fn response(method: &ApiMethod) -> Value {
+    let json_schema = match (method.types.response(), &method.response_name) {
+        (Some(response), Some(name)) if response.is_list => {
+            Some(json!({ "type": "array", "items": reference(name) }))
+        }
+        (Some(_), Some(name)) => Some(reference(name)),
+        _ => method.types.value_response().map(field_schema),
+    };
+
+    let (content_type, schema) = match (json_schema, method.return_type) {
+        (Some(schema), _) => ("application/json", schema),
+        (None, ReturnType::Unit) => return json!({ "description": "Ok." }),
+        (None, ReturnType::Integer) => ("text/plain", json!({ "type": "integer" })),
+        // e.g. `v4.1.3`
+        (None, ReturnType::Version) => (
+            "text/plain",
+            json!({ "type": "string", "format": "version" }),
+        ),
+        // e.g. `1` or `0`
+        (None, ReturnType::Bool) => ("text/plain", json!({ "type": "integer", "enum": [0, 1] })),
+        (None, _) => ("text/plain", json!({ "type": "string" })),
+    };
+
+    json!({
+        "description": "Ok.",
+        "content": { content_type: { "schema": schema } },
+    })
+}
+
+/// The types defined by the method. Types defined by multiple methods, e.g. `FilePriority`,
+/// are identical, so the first one is kept.
+fn add_schemas(schemas: &mut Map<String, Value>, method: &ApiMethod) {
+    let mut add = |name: &str, schema: Value| {
+        schemas.entry(name.to_string()).or_insert(schema);
+    };
+
+    for composite_type in &method.types.composite_types {
+        match composite_type {
+            CompositeType::Enum(enum_) => add(&enum_.name, enum_schema(enum_)),
+            CompositeType::Object(object) | CompositeType::JsonParameter(object) => {
+                add(&object.name, object_schema(&object.types))
+            }
+            CompositeType::Response(response) => {
+                if let Some(name) = &method.response_name {
+                    add(name, object_schema(&response.types));
+                }
+            }
+            CompositeType::Parameters(_)
+            | CompositeType::PossibleFields(_)
+            | CompositeType::ValueResponse(_)
+            | CompositeType::Shared(_) => {}
+        }
+    }
+}
+
+fn object_schema(fields: &[types::Type]) -> Value {
+    let properties: Map<String, Value> = fields
+        .iter()
+        .map(|field| (field.get_type_info().name.clone(), field_schema(field)))
+        .collect();
+    let required: Vec<&str> = fields
+        .iter()
+        .filter(|field| !field.is_optional())
+        .map(|field| field.get_type_info().name.as_str())
+        .collect();
+
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+
+    schema
+}
+
+fn enum_schema(enum_: &parser::Enum) -> Value {
+    let (paths, values): (Vec<_>, Vec<_>) = enum_
+        .values
+        .iter()
+        .partition(|value| value.is_path_to_download_to());
+
+    let integers: Option<Vec<i64>> = values.iter().map(|value| value.as_integer()).collect();
+    let mut schema = match integers {
+        Some(integers) => json!({ "type": "integer", "enum": integers }),
+        None => json!({
+            "type": "string",
+            "enum": values
+                .iter()
+                .map(|value| value.original_value.as_str())
+                .collect::<Vec<_>>(),
+        }),
+    };
+    // the names of the variants, as understood by most OpenAPI generators
+    schema["x-enum-varnames"] = json!(values
+        .iter()
+        .map(|value| value.value.as_str())
+        .collect::<Vec<_>>());
+    if values.iter().any(|value| value.description.is_some()) {
+        schema["x-enum-descriptions"] = json!(values
+            .iter()
+            .map(|value| value.description.as_deref().unwrap_or_default())
+            .collect::<Vec<_>>());
+    }
+
+    // e.g. `scan_dirs`, where a path may be given instead of one of the integers
+    if let Some(path) = paths.first() {
+        schema = json!({
+            "oneOf": [
+                schema,
+                with_description(json!({ "type": "string" }), &path.description),
+            ]
+        });
+    }
+
+    let descriptions: Vec<String> = enum_
+        .values
+        .iter()
+        .filter_map(|value| {
+            let description = value.description.as_ref()?;
+            if value.is_path_to_download_to() {
+                Some(format!("- a path: {description}"))
+            } else {
+                Some(format!("- `{}`: {}", value.original_value, description))
+            }
+        })
+        .collect();
+    let description = (!descriptions.is_empty()).then(|| descriptions.join("\n"));
+
+    with_description(schema, &description)
+}
+
+fn field_schema(field: &types::Type) -> Value {
+    let schema = match field {
+        types::Type::Number(type_info) => match types::integer_type(&type_info.name) {
+            "i64" => json!({ "type": "integer", "format": "int64" }),
+            "u8" => json!({ "type": "integer", "minimum": 0, "maximum": u8::MAX }),
+            _ => json!({ "type": "integer", "minimum": 0, "maximum": u16::MAX }),
+        },
+        types::Type::Float(_) => json!({ "type": "number" }),
+        types::Type::Bool(_) => json!({ "type": "boolean" }),
+        types::Type::String(_) | types::Type::StringArray(_) => json!({ "type": "string" }),
+        types::Type::JsonObject(_) => json!({ "type": "object" }),
+        types::Type::Object(object) => match &object.ref_type {
+            RefType::String(name) => reference(name),
+            RefType::Map(_, value) => {
+                json!({ "type": "object", "additionalProperties": reference(value) })
+            }
+        },
+        types::Type::Enum(enum_ref) => reference(&enum_ref.name),
+    };
+
+    let schema = if field.is_list() {
+        json!({ "type": "array", "items": schema })
+    } else {
+        schema
+    };
+
+    with_description(schema, &field.get_type_info().description)
+}
+
+/// A reference to a generated type or the schema of a primitive, e.g. the `i64` of a map.
+fn reference(name: &str) -> Value {
+    match name {
+        "String" => json!({ "type": "string" }),
+        "i64" => json!({ "type": "integer", "format": "int64" }),
+        "f64" => json!({ "type": "number" }),
+        "bool" => json!({ "type": "boolean" }),
+        _ => json!({ "$ref": format!("{SCHEMAS}{name}") }),
+    }
+}
+
+/// `$ref` siblings are ignored by OpenAPI 3.0, so references are wrapped in `allOf` in order
+/// to keep the description.
+fn with_description(schema: Value, description: &Option<String>) -> Value {
+    let description = match description {
+        Some(description) => description,
+        None => return schema,
+    };
+
+    let mut schema = if schema.get("$ref").is_some() {
+        json!({ "allOf": [schema] })
+    } else {
+        schema
+    };
+    schema["description"] = json!(description);
+
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::md_parser;
+
+    fn document_from_api() -> Value {
+        let content = include_str!("../../api-4_1.md");
+        let tree = md_parser::TokenTreeFactory::create(content).unwrap();
+        let version = parser::parse_api_version(&tree).unwrap();
+        let groups = parser::parse_api_groups(tree).unwrap();
+
+        document(&groups, &version)
+    }
+
+    #[test]
+    fn it_works() {
+        let document = document_from_api();
+
+        let output = serde_json::to_string_pretty(&document).unwrap();
+        std::fs::write("openapi.json", output).unwrap();
+    }
+
+    #[test]
+    fn responses_refer_to_the_response_types() {
+        let document = document_from_api();
+
+        let info = &document["paths"]["/api/v2/torrents/info"]["post"];
+        assert_eq!(
+            info["responses"]["200"]["content"]["application/json"]["schema"],
+            json!({ "type": "array", "items": { "$ref": "#/components/schemas/TorrentInfo" } })
+        );
+        assert_eq!(
+            info["requestBody"]["content"]["multipart/form-data"]["schema"]["properties"]["filter"]
+                ["allOf"][0],
+            json!({ "$ref": "#/components/schemas/TorrentFilter" })
+        );
+        assert_eq!(document["info"]["version"], "2.8.3");
+    }
+
+    #[test]
+    fn scan_dirs_are_integers_or_a_path() {
+        let document = document_from_api();
+
+        let scan_dirs = &document["components"]["schemas"]["ScanDirs"];
+        assert_eq!(scan_dirs["oneOf"][0]["type"], "integer");
+        assert_eq!(scan_dirs["oneOf"][0]["enum"], json!([0, 1]));
+        assert_eq!(
+            scan_dirs["oneOf"][1],
+            json!({ "type": "string", "description": "Download to this path" })
+        );
+    }
+
+    #[test]
+    fn all_references_are_defined() {
+        fn references(value: &Value, output: &mut Vec<String>) {
+            match value {
+                Value::Object(object) => {
+                    if let Some(Value::String(reference)) = object.get("$ref") {
+                        output.push(reference.trim_start_matches(SCHEMAS).to_string());
+                    }
+                    object.values().for_each(|value| references(value, output));
+                }
+                Value::Array(array) => array.iter().for_each(|value| references(value, output)),
+                _ => {}
+            }
+        }
+
+        let document = document_from_api();
+        let mut names = Vec::new();
+        references(&document, &mut names);
+
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+        for name in names {
+            assert!(schemas.contains_key(&name), "{name} is not defined");
+        }
+    }
+}
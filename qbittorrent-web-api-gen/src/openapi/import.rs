@@ -0,0 +1,844 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    error::{ParseError, ParseResult},
+    parser::{
+        self, ApiGroup, ApiMethod, CompositeType, CompositeTypes, Enum, EnumValue, ReturnType,
+        SharedType, TypeWithName, TypeWithoutName,
+    },
+    types::{self, EnumRef, Object, RefType, TypeInfo},
+};
+
+use super::SCHEMAS;
+
+/// The path of an OpenAPI document relative to the crate deriving the api.
+pub fn path(path: &str) -> PathBuf {
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => Path::new(&dir).join(path),
+        None => path.into(),
+    }
+}
+
+/// Reads an OpenAPI 3 document in JSON, or in YAML with the `yaml` feature.
+pub fn read(path: &Path) -> ParseResult<Value> {
+    let content = std::fs::read_to_string(path).map_err(|err| {
+        ParseError::new(format!(
+            "a readable OpenAPI document at `{}` ({err})",
+            path.display()
+        ))
+    })?;
+
+    parse(&content)
+}
+
+#[cfg(feature = "yaml")]
+fn parse(content: &str) -> ParseResult<Value> {
+    // JSON is valid YAML as well
+    serde_yaml::from_str(content)
+        .map_err(|err| ParseError::new(format!("an OpenAPI document in JSON or YAML ({err})")))
+}
+
+#[cfg(not(feature = "yaml"))]
+fn parse(content: &str) -> ParseResult<Value> {
+    serde_json::from_str(content).map_err(|err| {
+        ParseError::new(format!(
+            "an OpenAPI document in JSON, YAML requires the `yaml` feature ({err})"
+        ))
+    })
+}
+
+/// The path of the first server, e.g. `/api/v2` of `http://localhost:8080/api/v2/`.
+fn server_base_path(document: &Value) -> &str {
+    let url = document
+        .pointer("/servers/0/url")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let path = match url.split_once("://") {
+        Some((_, host_and_path)) => host_and_path
+            .find('/')
+            .map_or("", |index| &host_and_path[index..]),
+        None => url,
+    };
+
+    path.trim_end_matches('/')
+}
+
+/// The version of the documented api, e.g. `2.8.3`.
+pub fn api_version(document: &Value) -> Option<String> {
+    document
+        .pointer("/info/version")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Parses the operations of an OpenAPI document into the same model as the markdown, e.g.
+/// the operation of `/api/v2/torrents/info` tagged with "Torrent management" becomes the
+/// `info` method of the `torrent_management` group. Paths may also be relative to the url of
+/// the server, e.g. `/torrents/info` with the server `http://localhost:8080/api/v2`.
+pub fn parse_api_groups(document: &Value) -> ParseResult<Vec<ApiGroup>> {
+    let paths = document
+        .get("paths")
+        .and_then(Value::as_object)
+        .ok_or_else(|| ParseError::new("the `paths` of the OpenAPI document"))?;
+    let empty = Map::new();
+    let schemas = document
+        .pointer("/components/schemas")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+
+    let mut groups: Vec<ApiGroup> = Vec::new();
+    // types are generated by the first method using them and shared with the others
+    let mut defined: Vec<SharedType> = Vec::new();
+    let base_path = server_base_path(document);
+
+    for (path, item) in paths {
+        let in_path = |err: ParseError| err.in_section(Some(path));
+        let full_path = if path.starts_with("/api/v2/") {
+            path.clone()
+        } else {
+            format!("{base_path}{path}")
+        };
+        let (group_url, method_url) = full_path
+            .strip_prefix("/api/v2/")
+            .and_then(|path| path.split_once('/'))
+            .ok_or_else(|| {
+                in_path(ParseError::new(
+                    "a path like `/api/v2/{group}/{method}`, or `/{group}/{method}` with a server url ending in `/api/v2`",
+                ))
+            })?;
+        let operation = item
+            .get("post")
+            .or_else(|| item.get("get"))
+            .ok_or_else(|| in_path(ParseError::new("a `post` or `get` operation")))?;
+
+        let tag = operation.pointer("/tags/0").and_then(Value::as_str);
+        let group_name = tag.map_or_else(|| group_url.to_string(), parser::group_name);
+        let group = match groups.iter().position(|group| group.name == group_name) {
+            Some(index) => &mut groups[index],
+            None => {
+                groups.push(ApiGroup {
+                    name: group_name.clone(),
+                    methods: Vec::new(),
+                    description: tag.and_then(|tag| tag_description(document, tag)),
+                    url: group_url.to_string(),
+                    span: None,
+                });
+                groups.last_mut().expect("a group was just added")
+            }
+        };
+
+        let method = Operation::new(schemas)
+            .parse(&group_name, method_url, operation, &mut defined)
+            .map_err(in_path)?;
+        group.methods.push(method);
+    }
+
+    Ok(groups)
+}
+
+fn tag_description(document: &Value, tag: &str) -> Option<String> {
+    document
+        .get("tags")?
+        .as_array()?
+        .iter()
+        .find(|t| t.get("name").and_then(Value::as_str) == Some(tag))?
+        .get("description")?
+        .as_str()
+        .map(str::to_string)
+}
+
+struct Operation<'a> {
+    schemas: &'a Map<String, Value>,
+    /// The names of the schemas used by the method, in the order they are referenced.
+    references: Vec<String>,
+}
+
+impl<'a> Operation<'a> {
+    fn new(schemas: &'a Map<String, Value>) -> Self {
+        Self {
+            schemas,
+            references: Vec::new(),
+        }
+    }
+
+    fn parse(
+        mut self,
+        group_name: &str,
+        method_url: &str,
+        operation: &'a Value,
+        defined: &mut Vec<SharedType>,
+    ) -> ParseResult<ApiMethod> {
+        let mut composite_types = Vec::new();
+
+        let parameters = self.parameters(operation)?;
+        if !parameters.is_empty() {
+            composite_types.push(CompositeType::Parameters(TypeWithoutName::new(
+                parameters, false,
+            )));
+        }
+        let json_parameter = self.json_parameter(operation)?;
+        let (response, response_name, return_type) = self.response(method_url, operation)?;
+
+        // the types which are generated by the method itself rather than defined by a schema
+        let mut local: Vec<String> = response_name.iter().cloned().collect();
+        if let Some(json_parameter) = json_parameter {
+            local.push(json_parameter.name.clone());
+            composite_types.push(CompositeType::JsonParameter(json_parameter));
+        }
+        composite_types.extend(response);
+
+        let mut index = 0;
+        while let Some(name) = self.references.get(index).cloned() {
+            index += 1;
+            if local.contains(&name) {
+                continue;
+            }
+            local.push(name.clone());
+
+            match defined.iter().find(|shared| shared.name == name) {
+                Some(shared) => composite_types.push(CompositeType::Shared(shared.clone())),
+                None => {
+                    composite_types.push(self.definition(&name)?);
+                    defined.push(SharedType::new(&name, group_name, method_url));
+                }
+            }
+        }
+        if let Some(name) = &response_name {
+            if !defined.iter().any(|shared| &shared.name == name) {
+                defined.push(SharedType::new(name, group_name, method_url));
+            }
+        }
+
+        let description = operation
+            .get("description")
+            .or_else(|| operation.get("summary"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        Ok(ApiMethod {
+            name: method_url.to_string(),
+            description,
+            url: method_url.to_string(),
+            types: CompositeTypes { composite_types },
+            return_type,
+            response_name,
+            anchor: None,
+            span: None,
+        })
+    }
+
+    /// Query parameters and the fields of the form, except for the json encoded one.
+    fn parameters(&mut self, operation: &'a Value) -> ParseResult<Vec<types::Type>> {
+        let mut parameters = Vec::new();
+
+        let query = operation.get("parameters").and_then(Value::as_array);
+        for parameter in query.into_iter().flatten() {
+            // e.g. headers and cookies
+            if parameter.get("in").and_then(Value::as_str) != Some("query") {
+                continue;
+            }
+
+            let name = parameter
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| ParseError::new("the name of a parameter"))?;
+            let is_optional = parameter.get("required") != Some(&Value::Bool(true));
+            let schema = parameter.get("schema").unwrap_or(&Value::Null);
+            let description = parameter.get("description").and_then(Value::as_str);
+            parameters.push(self.field(name, schema, is_optional, description)?);
+        }
+
+        if let Some((form, json_encoded)) = self.form(operation)? {
+            let fields = self.fields(form)?;
+            parameters.extend(
+                fields
+                    .into_iter()
+                    .filter(|field| Some(field.get_type_info().name.as_str()) != json_encoded),
+            );
+        }
+
+        // parameters are borrowed, which isn't supported for objects
+        match parameters.iter().find(|parameter| {
+            matches!(
+                parameter,
+                types::Type::Object(_) | types::Type::JsonObject(_)
+            )
+        }) {
+            Some(parameter) => Err(ParseError::new(format!(
+                "a primitive or enum type for the parameter `{}`",
+                parameter.get_type_info().name
+            ))),
+            None => Ok(parameters),
+        }
+    }
+
+    /// A form field which is a json encoded object, e.g. the `json` of `setPreferences`.
+    fn json_parameter(&mut self, operation: &'a Value) -> ParseResult<Option<TypeWithName>> {
+        let (form, name) = match self.form(operation)? {
+            Some((form, Some(name))) => (form, name),
+            _ => return Ok(None),
+        };
+
+        let property = form
+            .pointer(&format!("/properties/{name}"))
+            .map(unwrap_all_of)
+            .unwrap_or(&Value::Null);
+        let (type_name, schema) = self.reference(property)?.ok_or_else(|| {
+            ParseError::new(format!(
+                "a reference to the schema of the `{name}` parameter"
+            ))
+        })?;
+
+        Ok(Some(TypeWithName::new(&type_name, self.fields(schema)?)))
+    }
+
+    /// The schema of the form and the name of its json encoded field, if any.
+    fn form(&self, operation: &'a Value) -> ParseResult<Option<(&'a Value, Option<&'a str>)>> {
+        let content = match operation.pointer("/requestBody/content") {
+            Some(content) => content,
+            None => return Ok(None),
+        };
+        let media = match content
+            .get("multipart/form-data")
+            .or_else(|| content.get("application/x-www-form-urlencoded"))
+        {
+            Some(media) => media,
+            None => return Ok(None),
+        };
+
+        let json_encoded = media
+            .get("encoding")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .find(|(_, encoding)| {
+                encoding.get("contentType").and_then(Value::as_str) == Some("application/json")
+            })
+            .map(|(name, _)| name.as_str());
+        let schema = self.resolve(media.get("schema").unwrap_or(&Value::Null))?;
+
+        Ok(Some((schema, json_encoded)))
+    }
+
+    fn response(
+        &mut self,
+        method_url: &str,
+        operation: &'a Value,
+    ) -> ParseResult<(Option<CompositeType>, Option<String>, ReturnType)> {
+        let responses = operation.get("responses").and_then(Value::as_object);
+        let response = responses.and_then(|responses| {
+            responses.get("200").or_else(|| {
+                responses
+                    .iter()
+                    .find_map(|(status, response)| status.starts_with('2').then_some(response))
+            })
+        });
+        let content = match response
+            .and_then(|response| response.get("content"))
+            .and_then(Value::as_object)
+        {
+            Some(content) if !content.is_empty() => content,
+            _ => return Ok((None, None, ReturnType::Unit)),
+        };
+
+        if let Some(schema) = content
+            .get("application/json")
+            .and_then(|m| m.get("schema"))
+        {
+            let schema = unwrap_all_of(schema);
+            let (element, is_list) = array_element(schema);
+            if let Some((name, object)) = self.object_reference(element)? {
+                let response = TypeWithoutName::new(self.fields(object)?, is_list);
+                return Ok((
+                    Some(CompositeType::Response(response)),
+                    Some(name),
+                    ReturnType::Json,
+                ));
+            }
+
+            // e.g. `Vec<String>` or `HashMap<String, Category>`
+            let value = self.field(method_url, schema, false, None)?;
+            return Ok((
+                Some(CompositeType::ValueResponse(value)),
+                None,
+                ReturnType::Json,
+            ));
+        }
+
+        let schema = content
+            .get("text/plain")
+            .and_then(|media| media.get("schema"))
+            .unwrap_or(&Value::Null);
+        let return_type = match (
+            schema.get("type").and_then(Value::as_str),
+            schema.get("format").and_then(Value::as_str),
+        ) {
+            (Some("integer"), _) if schema.get("enum") == Some(&serde_json::json!([0, 1])) => {
+                ReturnType::Bool
+            }
+            (Some("integer"), _) => ReturnType::Integer,
+            (Some("string"), Some("version")) => ReturnType::Version,
+            _ => ReturnType::Text,
+        };
+
+        Ok((None, None, return_type))
+    }
+
+    /// An enum or an object defined by the schemas of the document.
+    fn definition(&mut self, name: &str) -> ParseResult<CompositeType> {
+        let schema = self.schema(name)?;
+        if is_enum(schema) {
+            return Ok(CompositeType::Enum(enum_(name, schema)));
+        }
+
+        Ok(CompositeType::Object(TypeWithName::new(
+            name,
+            self.fields(schema)?,
+        )))
+    }
+
+    fn fields(&mut self, schema: &'a Value) -> ParseResult<Vec<types::Type>> {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+
+        schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .map(|(name, property)| {
+                self.field(name, property, !required.contains(&name.as_str()), None)
+            })
+            .collect()
+    }
+
+    fn field(
+        &mut self,
+        name: &str,
+        schema: &'a Value,
+        is_optional: bool,
+        description: Option<&str>,
+    ) -> ParseResult<types::Type> {
+        // e.g. `{ "allOf": [{ "$ref": ... }], "description": ... }`
+        let description = description.or_else(|| schema.get("description")?.as_str());
+        let (schema, is_list) = array_element(unwrap_all_of(schema));
+        let description = description
+            .or_else(|| schema.get("description")?.as_str())
+            .map(str::to_string);
+        let type_info = TypeInfo::new(name, is_optional, is_list, description);
+
+        if let Some((type_name, target)) = self.reference(schema)? {
+            if is_enum(target) {
+                self.references.push(type_name.clone());
+                return Ok(types::Type::Enum(EnumRef {
+                    type_info,
+                    name: type_name,
+                }));
+            }
+            if is_object(target) {
+                self.references.push(type_name.clone());
+                return Ok(types::Type::Object(Object {
+                    type_info,
+                    ref_type: RefType::String(type_name),
+                }));
+            }
+
+            // e.g. an alias of a primitive type
+            return self.field(name, target, is_optional, type_info.description.as_deref());
+        }
+
+        match schema.get("type").and_then(Value::as_str) {
+            Some("integer") => Ok(types::Type::Number(type_info)),
+            Some("number") => Ok(types::Type::Float(type_info)),
+            Some("boolean") => Ok(types::Type::Bool(type_info)),
+            Some("string") => Ok(types::Type::String(type_info)),
+            Some("object") => match schema.get("additionalProperties") {
+                Some(values) if values.is_object() => Ok(types::Type::Object(Object {
+                    type_info,
+                    ref_type: RefType::Map("String".into(), self.value_type(values)?),
+                })),
+                _ => Ok(types::Type::JsonObject(type_info)),
+            },
+            _ => Err(ParseError::new(format!("a type for `{name}`"))),
+        }
+    }
+
+    /// The type of the values of a map, e.g. `i64` or `Category`.
+    fn value_type(&mut self, schema: &'a Value) -> ParseResult<String> {
+        let schema = unwrap_all_of(schema);
+        if let Some((name, _)) = self.reference(schema)? {
+            self.references.push(name.clone());
+            return Ok(name);
+        }
+
+        match schema.get("type").and_then(Value::as_str) {
+            Some("integer") => Ok("i64".into()),
+            Some("number") => Ok("f64".into()),
+            Some("boolean") => Ok("bool".into()),
+            Some("string") => Ok("String".into()),
+            _ => Err(ParseError::new(
+                "a primitive or referenced type for the values of a map",
+            )),
+        }
+    }
+
+    /// A reference to an object with fields, e.g. the element of a list response.
+    fn object_reference(&self, schema: &'a Value) -> ParseResult<Option<(String, &'a Value)>> {
+        Ok(self
+            .reference(schema)?
+            .filter(|(_, target)| !is_enum(target) && is_object(target)))
+    }
+
+    /// The name and the schema of a `$ref`, e.g. `#/components/schemas/TorrentInfo`.
+    fn reference(&self, schema: &'a Value) -> ParseResult<Option<(String, &'a Value)>> {
+        let name = match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => reference.strip_prefix(SCHEMAS).ok_or_else(|| {
+                ParseError::new(format!("a reference into `{SCHEMAS}`, found `{reference}`"))
+            })?,
+            None => return Ok(None),
+        };
+
+        Ok(Some((name.to_string(), self.schema(name)?)))
+    }
+
+    fn resolve(&self, schema: &'a Value) -> ParseResult<&'a Value> {
+        Ok(match self.reference(schema)? {
+            Some((_, target)) => target,
+            None => schema,
+        })
+    }
+
+    fn schema(&self, name: &str) -> ParseResult<&'a Value> {
+        self.schemas
+            .get(name)
+            .ok_or_else(|| ParseError::new(format!("a schema named `{name}`")))
+    }
+}
+
+/// `oneOf` an enum and a string, e.g. `scan_dirs` values which may be a path instead.
+fn enum_or_path(schema: &Value) -> Option<(&Value, &Value)> {
+    match schema.get("oneOf")?.as_array()?.as_slice() {
+        [enum_, path]
+            if enum_.get("enum").is_some()
+                && path.get("type").and_then(Value::as_str) == Some("string") =>
+        {
+            Some((enum_, path))
+        }
+        _ => None,
+    }
+}
+
+fn is_enum(schema: &Value) -> bool {
+    schema.get("enum").is_some() || enum_or_path(schema).is_some()
+}
+
+fn enum_(name: &str, schema: &Value) -> Enum {
+    if let Some((schema, path)) = enum_or_path(schema) {
+        let mut enum_ = enum_(name, schema);
+        let description = path.get("description").and_then(Value::as_str);
+        enum_.values.push(EnumValue::path_to_download_to(
+            description.map(str::to_string),
+        ));
+        return enum_;
+    }
+
+    let strings = |key: &str| -> Vec<&str> {
+        schema
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|value| value.as_str().unwrap_or_default())
+            .collect()
+    };
+    let names = strings("x-enum-varnames");
+    let descriptions = strings("x-enum-descriptions");
+
+    let values = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, value)| {
+            let original_value = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            let description = descriptions
+                .get(index)
+                .filter(|description| !description.is_empty())
+                .map(|description| description.to_string());
+
+            match names.get(index) {
+                Some(name) if !name.is_empty() => EnumValue {
+                    description,
+                    value: EnumValue::variant_name(name),
+                    original_value,
+                },
+                _ => EnumValue::new(&original_value, description),
+            }
+        })
+        .collect();
+
+    Enum {
+        name: name.to_string(),
+        values,
+    }
+}
+
+/// The schema of the elements of an array and whether it was an array.
+fn array_element(schema: &Value) -> (&Value, bool) {
+    match schema.get("items") {
+        Some(items) if schema.get("type").and_then(Value::as_str) == Some("array") => {
+            (unwrap_all_of(items), true)
+        }
+        _ => (schema, false),
+    }
+}
+
+/// `allOf` with a single schema, which is used to add a description to a reference.
+fn unwrap_all_of(schema: &Value) -> &Value {
+    match schema
+        .get("allOf")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+    {
+        Some([schema]) => schema,
+        _ => schema,
+    }
+}
+
+fn is_object(schema: &Value) -> bool {
+    schema.get("properties").is_some()
+        || (schema.get("type").and_then(Value::as_str) == Some("object")
+            && schema.get("additionalProperties").is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{md_parser, openapi};
+
+    fn groups_from_api() -> Vec<ApiGroup> {
+        let content = include_str!("../../api-4_1.md");
+        let tree = md_parser::TokenTreeFactory::create(content).unwrap();
+
+        parser::parse_api_groups(tree).unwrap()
+    }
+
+    #[test]
+    fn exported_document_is_parsed_to_the_same_model() {
+        let document = openapi::document(&groups_from_api(), "2.8.3");
+        let groups = parse_api_groups(&document).unwrap();
+
+        assert_eq!(openapi::document(&groups, "2.8.3"), document);
+    }
+
+    #[test]
+    fn types_are_shared_with_later_methods() {
+        let document = openapi::document(&groups_from_api(), "2.8.3");
+        let groups = parse_api_groups(&document).unwrap();
+
+        let method = |group: &str, method: &str| {
+            groups
+                .iter()
+                .find(|g| g.name == group)
+                .and_then(|g| g.methods.iter().find(|m| m.name == method))
+                .unwrap()
+        };
+
+        let info = method("torrent_management", "info");
+        assert_eq!(info.response_name.as_deref(), Some("TorrentInfo"));
+        assert!(info.types.response().unwrap().is_list);
+
+        let shared: Vec<&SharedType> = groups
+            .iter()
+            .flat_map(|g| &g.methods)
+            .flat_map(|m| m.types.shared())
+            .collect();
+        assert!(!shared.is_empty());
+
+        for shared in shared {
+            let defining_method = method(&shared.group, &shared.method);
+            let type_names: Vec<&String> = defining_method
+                .response_name
+                .iter()
+                .chain(defining_method.types.objects().iter().map(|o| &o.name))
+                .chain(defining_method.types.enums().iter().map(|e| &e.name))
+                .collect();
+            assert!(
+                type_names.contains(&&shared.name),
+                "{shared:?} is not defined"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn yaml_documents_are_supported() {
+        let document: Value = serde_yaml::from_str(
+            r#"
+openapi: 3.0.3
+info:
+  title: qBittorrent Web API
+  version: 2.8.3
+tags:
+  - name: Transfer info
+    description: All Transfer info API methods are under "transfer"
+paths:
+  /api/v2/transfer/setDownloadLimit:
+    post:
+      tags: [Transfer info]
+      requestBody:
+        content:
+          application/x-www-form-urlencoded:
+            schema:
+              type: object
+              required: [limit]
+              properties:
+                limit:
+                  type: integer
+                  description: The global download speed limit to set in bytes/second
+      responses:
+        "200":
+          description: Ok.
+  /api/v2/transfer/speedLimitsMode:
+    get:
+      tags: [Transfer info]
+      responses:
+        "200":
+          description: Ok.
+          content:
+            text/plain:
+              schema: { type: integer, enum: [0, 1] }
+"#,
+        )
+        .unwrap();
+
+        let groups = parse_api_groups(&document).unwrap();
+        assert_eq!(api_version(&document).as_deref(), Some("2.8.3"));
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "transfer_info");
+        assert_eq!(groups[0].url, "transfer");
+
+        let set_download_limit = &groups[0].methods[0];
+        assert_eq!(set_download_limit.return_type, ReturnType::Unit);
+        let parameters = set_download_limit.types.mandatory_params();
+        assert!(matches!(parameters[..], [types::Type::Number(_)]));
+
+        assert_eq!(groups[0].methods[1].return_type, ReturnType::Bool);
+    }
+
+    #[test]
+    fn enums_without_names_get_valid_variants() {
+        let document = serde_json::json!({
+            "paths": {
+                "/api/v2/torrents/files": {
+                    "post": {
+                        "tags": ["Torrent management"],
+                        "responses": {
+                            "200": {
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/TorrentFile" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "TorrentFile": {
+                        "type": "object",
+                        "properties": {
+                            "priority": { "$ref": "#/components/schemas/FilePriority" },
+                            "state": { "$ref": "#/components/schemas/TorrentState" }
+                        }
+                    },
+                    "FilePriority": { "type": "integer", "enum": [0, 1, 6, 7, -1] },
+                    "TorrentState": {
+                        "type": "string",
+                        "enum": ["stalled-uploading", "pausedUP", "2160p"]
+                    }
+                }
+            }
+        });
+
+        let groups = parse_api_groups(&document).unwrap();
+
+        let enums = groups[0].methods[0].types.enums();
+        let variants: Vec<Vec<&str>> = enums
+            .iter()
+            .map(|e| e.values.iter().map(|value| value.value.as_str()).collect())
+            .collect();
+        assert_eq!(
+            variants,
+            vec![
+                vec!["Value0", "Value1", "Value6", "Value7", "ValueMinus1"],
+                vec!["StalledUploading", "PausedUP", "Value2160p"],
+            ]
+        );
+        for variant in variants.into_iter().flatten() {
+            assert!(syn::parse_str::<syn::Ident>(variant).is_ok(), "{variant}");
+        }
+        assert_eq!(enums[1].values[0].original_value, "stalled-uploading");
+    }
+
+    #[test]
+    fn paths_are_relative_to_the_server_url() {
+        let document = serde_json::json!({
+            "servers": [{ "url": "http://localhost:8080/api/v2/" }],
+            "paths": {
+                "/torrents/info": {
+                    "get": { "tags": ["Torrent management"], "responses": {} }
+                },
+                "/api/v2/app/version": {
+                    "get": { "tags": ["Application"], "responses": {} }
+                }
+            }
+        });
+
+        let groups = parse_api_groups(&document).unwrap();
+
+        let urls: Vec<(&str, &str)> = groups
+            .iter()
+            .flat_map(|g| g.methods.iter().map(|m| (g.url.as_str(), m.url.as_str())))
+            .collect();
+        assert_eq!(urls, vec![("torrents", "info"), ("app", "version")]);
+    }
+
+    #[test]
+    fn unknown_references_are_reported() {
+        let document = serde_json::json!({
+            "paths": {
+                "/api/v2/app/buildInfo": {
+                    "post": {
+                        "responses": {
+                            "200": {
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/BuildInfo" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let err = parse_api_groups(&document).unwrap_err();
+        assert_eq!(err.section.as_deref(), Some("/api/v2/app/buildInfo"));
+        assert_eq!(err.expected, "a schema named `BuildInfo`");
+    }
+}
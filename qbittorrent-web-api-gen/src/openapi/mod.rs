@@ -0,0 +1,7 @@
+mod export;
+mod import;
+
+pub use export::document;
+pub use import::{api_version, parse_api_groups, path, read};
+
+const SCHEMAS: &str = "#/components/schemas/";
@@ -0,0 +1,65 @@
+use case::CaseExt;
+
+use crate::{
+    error::ParseResult,
+    md_parser::{self, Inline},
+};
+
+use super::{ApiMethod, CompositeType, SharedType, TypeWithName};
+
+impl md_parser::TokenTree {
+    /// Some methods, e.g. `setPreferences`, take a single `json` parameter whose
+    /// fields are documented by another method in the same group:
+    ///
+    /// "For a list of possible preference options see [Get application preferences](#get-application-preferences)"
+    ///
+    /// The enums used by the fields are shared with the referenced method.
+    pub fn parse_json_parameter(
+        &self,
+        method: &md_parser::TokenTree,
+    ) -> ParseResult<Vec<CompositeType>> {
+        let referenced_method = match method
+            .find_content_starts_with("A json object with key-value pairs")
+            .and_then(|_| self.find_child_by_anchor(&method.find_see_also_anchor()?))
+            .map(ApiMethod::try_new)
+            .transpose()?
+            .flatten()
+        {
+            Some(referenced_method) => referenced_method,
+            None => return Ok(vec![]),
+        };
+
+        let types = &referenced_method.types;
+        let fields = match types.possible_fields() {
+            Some(fields) => fields.types.clone(),
+            None => return Ok(vec![]),
+        };
+
+        let json_parameter = CompositeType::JsonParameter(TypeWithName::new(
+            &format!("{}Patch", referenced_method.name.to_camel()),
+            fields,
+        ));
+
+        let group_name = self.name()?;
+        let enums = types.enums().into_iter().map(|e| {
+            CompositeType::Shared(SharedType::new(
+                &e.name,
+                &group_name,
+                &referenced_method.name,
+            ))
+        });
+
+        Ok(std::iter::once(json_parameter).chain(enums).collect())
+    }
+
+    fn find_see_also_anchor(&self) -> Option<String> {
+        self.content.iter().find_map(|row| {
+            row.inlines().windows(2).find_map(|inlines| match inlines {
+                [Inline::Text(text), Inline::Link(link)] if text.ends_with("see ") => {
+                    link.anchor().map(str::to_string)
+                }
+                _ => None,
+            })
+        })
+    }
+}
@@ -0,0 +1,77 @@
+use case::CaseExt;
+
+use crate::{
+    error::ParseResult,
+    md_parser,
+    types::{self, EnumRef},
+};
+
+use super::{CompositeType, Enum, Tables};
+
+impl md_parser::TokenTree {
+    /// Responses which are a JSON array of primitive values rather than objects,
+    /// e.g. `pieceStates`, `pieceHashes` or `tags`.
+    pub fn parse_array_response(
+        &self,
+        method_url: &str,
+        tables: &Tables,
+    ) -> ParseResult<Vec<CompositeType>> {
+        let element = match self
+            .described_array_element()
+            .or_else(|| self.example_array_element())
+        {
+            Some(element) => element,
+            None => return Ok(vec![]),
+        };
+
+        let type_info = types::TypeInfo::new(method_url, false, true, None);
+        let types = match (element, tables.get("Value meanings are defined as below")) {
+            // e.g. the states of `pieceStates`
+            (ArrayElement::Integer, Some(table)) => {
+                let name = method_url.trim_end_matches('s').to_camel();
+                let enum_ = Enum::new(&name, table);
+                let type_ = types::Type::Enum(EnumRef { type_info, name });
+
+                vec![
+                    CompositeType::Enum(enum_),
+                    CompositeType::ValueResponse(type_),
+                ]
+            }
+            (ArrayElement::Integer, None) => {
+                vec![CompositeType::ValueResponse(types::Type::Number(type_info))]
+            }
+            (ArrayElement::String, _) => {
+                vec![CompositeType::ValueResponse(types::Type::String(type_info))]
+            }
+        };
+
+        Ok(types)
+    }
+
+    /// e.g. "otherwise, an array of hashes (strings) of all pieces"
+    fn described_array_element(&self) -> Option<ArrayElement> {
+        let re = regex!(r"an array of \w+ \((integers|strings)\)");
+
+        self.texts()
+            .find_map(|text| re.captures(text))
+            .and_then(|captures| match &captures[1] {
+                "integers" => Some(ArrayElement::Integer),
+                "strings" => Some(ArrayElement::String),
+                _ => None,
+            })
+    }
+
+    /// e.g. "Returns all tags in JSON format" followed by an example such as `["Tag 1", "Tag 2"]`
+    fn example_array_element(&self) -> Option<ArrayElement> {
+        match self.json_example()?.as_array()?.first()? {
+            serde_json::Value::String(_) => Some(ArrayElement::String),
+            serde_json::Value::Number(_) => Some(ArrayElement::Integer),
+            _ => None,
+        }
+    }
+}
+
+enum ArrayElement {
+    Integer,
+    String,
+}
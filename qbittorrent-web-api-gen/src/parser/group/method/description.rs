@@ -6,24 +6,25 @@ impl md_parser::TokenTree {
             .content
             .iter()
             // skip until we get to the "Returns:" text
-            .skip_while(|row| match row {
+            .skip_while(|row| match &row.value {
                 MdContent::Asterisk(text) => !text.starts_with("Returns:"),
                 _ => true,
             })
             // there is one space before the table
             .skip(2)
-            .skip_while(|row| match row {
+            .skip_while(|row| match &row.value {
                 MdContent::Text(text) => !text.is_empty(),
                 _ => true,
             })
             // and there is one space after the table
             .skip(1)
             // then what is left should be the description
-            .flat_map(|row| match row {
-                MdContent::Text(text) => Some(text),
+            .flat_map(|row| match &row.value {
+                MdContent::Text(_) | MdContent::CodeBlock(_) | MdContent::ListItem(_) => {
+                    Some(row.inner_value_as_string())
+                }
                 _ => None,
             })
-            .cloned()
             .collect::<Vec<String>>()
             .join("\n")
             .trim()
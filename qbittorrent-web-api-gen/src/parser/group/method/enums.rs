@@ -0,0 +1,161 @@
+use case::CaseExt;
+
+use crate::types;
+
+use super::{CompositeType, CompositeTypes, Enum, EnumValue, SharedType};
+
+impl CompositeTypes {
+    /// Types fields which only accept a fixed set of values as enums. The values are
+    /// either documented in a "Possible values of" table, inline in the description,
+    /// e.g. "Allowed state filters: `all`, `downloading`, ...", or, for `sort`, are the
+    /// fields of the response.
+    pub fn link_enums(&mut self) {
+        let table_enums: Vec<String> = self.enums().iter().map(|e| e.name.clone()).collect();
+        let response_fields: Vec<String> = self
+            .response()
+            .map(|response| {
+                response
+                    .types
+                    .iter()
+                    .map(|field| field.get_type_info().name.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut inline_enums: Vec<Enum> = Vec::new();
+
+        for composite_type in &mut self.composite_types {
+            let (fields, is_parameters) = match composite_type {
+                CompositeType::Parameters(p) => (&mut p.types, true),
+                CompositeType::Response(p) => (&mut p.types, false),
+                CompositeType::Object(p) => (&mut p.types, false),
+                CompositeType::PossibleFields(p) => (&mut p.types, false),
+                _ => continue,
+            };
+
+            for field in fields.iter_mut() {
+                let name = field.get_type_info().name.to_camel();
+
+                if table_enums.contains(&name) {
+                    *field = field.to_enum(&name);
+                    continue;
+                }
+
+                let values = match field.inline_values() {
+                    Some(values) => values,
+                    None if is_parameters && field.refers_to_response_fields() => {
+                        response_fields.clone()
+                    }
+                    None => continue,
+                };
+
+                if is_boolean(&values) {
+                    *field = field.to_bool();
+                    continue;
+                }
+
+                if !inline_enums.iter().any(|e| e.name == name) {
+                    inline_enums.push(Enum::from_values(&name, &values));
+                }
+
+                *field = field.to_enum(&name);
+            }
+        }
+
+        self.composite_types
+            .extend(inline_enums.into_iter().map(CompositeType::Enum));
+    }
+
+    /// Types the parameter as an enum which is defined by another method.
+    pub fn link_parameter_enum(&mut self, parameter_name: &str, enum_: SharedType) {
+        let parameters = self
+            .composite_types
+            .iter_mut()
+            .find_map(|type_| match type_ {
+                CompositeType::Parameters(p) => Some(p),
+                _ => None,
+            });
+
+        let parameter = match parameters.and_then(|p| {
+            p.types
+                .iter_mut()
+                .find(|param| param.get_type_info().name == parameter_name)
+        }) {
+            Some(parameter) => parameter,
+            None => return,
+        };
+
+        *parameter = parameter.to_enum(&enum_.name);
+        self.add_shared(enum_);
+    }
+}
+
+impl Enum {
+    fn from_values(name: &str, values: &[String]) -> Self {
+        Enum {
+            name: name.to_string(),
+            values: values
+                .iter()
+                .map(|value| EnumValue {
+                    description: None,
+                    value: EnumValue::variant_name(value),
+                    original_value: value.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl types::Type {
+    /// Extracts values listed in the description, e.g.
+    /// "Allowed state filters: `all`, `downloading`, `seeding`" or
+    /// "Current status of the search job (either `Running` or `Stopped`)".
+    fn inline_values(&self) -> Option<Vec<String>> {
+        let description = self.get_type_info().description.as_ref()?;
+        let re = regex!(r"(?:Allowed [\w ]+:|Possible values are|either) (.+)$");
+
+        let listed = re
+            .captures(description)?
+            .get(1)?
+            .as_str()
+            .replace("(default)", "");
+        let listed = listed.split(['.', ')', ';']).next()?;
+
+        let values: Vec<String> = listed
+            .split(',')
+            .flat_map(|value| value.split(" or "))
+            .map(|value| value.trim().to_string())
+            .collect();
+
+        let is_value = |value: &String| {
+            !value.is_empty()
+                && value
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        };
+        if values.len() < 2 || !values.iter().all(is_value) {
+            return None;
+        }
+
+        Some(values)
+    }
+
+    /// E.g. `sort` of `torrents/info`: "They can be sorted using any field of the
+    /// response's JSON array (which are documented below) as the sort key."
+    fn refers_to_response_fields(&self) -> bool {
+        self.get_type_info()
+            .description
+            .as_ref()
+            .map(|description| description.contains("any field of the response"))
+            .unwrap_or(false)
+    }
+}
+
+/// Lists such as "Possible values are `true`, `false`, unset (default)" are plain
+/// booleans where the parameter can be left out.
+fn is_boolean(values: &[String]) -> bool {
+    values.iter().any(|value| value == "true")
+        && values
+            .iter()
+            .all(|value| matches!(value.as_str(), "true" | "false" | "unset"))
+}
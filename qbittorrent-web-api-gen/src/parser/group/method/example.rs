@@ -0,0 +1,57 @@
+use crate::md_parser::{self, MdContent};
+
+impl md_parser::TokenTree {
+    /// The example of a response which isn't described by a table, e.g.
+    /// "Returns all tags in JSON format, e.g.:" or "Server reply (example):".
+    pub fn json_example(&self) -> Option<serde_json::Value> {
+        let code_block = self.example_rows().find_map(|row| match row {
+            MdContent::CodeBlock(code_block) => Some(code_block),
+            _ => None,
+        })?;
+
+        let body = match code_block.language.as_deref() {
+            Some("JSON") => code_block.code.as_str(),
+            // skip the headers of the http reply
+            _ => code_block
+                .code
+                .split_once("\n\n")
+                .map_or("", |(_headers, body)| body),
+        };
+
+        serde_json::from_str(body).ok()
+    }
+
+    /// The text following the example, e.g. "`8c21...` is the hash of the torrent and `338944`
+    /// its download speed limit in bytes per second".
+    pub fn json_example_description(&self) -> Option<String> {
+        self.example_rows()
+            .skip_while(|row| !matches!(row, MdContent::CodeBlock(_)))
+            .find_map(|row| match row {
+                MdContent::Text(text) if !text.is_empty() => Some(text.clone()),
+                _ => None,
+            })
+    }
+
+    fn example_rows(&self) -> impl Iterator<Item = &MdContent> {
+        self.content
+            .iter()
+            .map(|row| &row.value)
+            .skip_while(|row| match row {
+                MdContent::Text(text) => {
+                    !((text.starts_with("Returns all") && text.contains("JSON"))
+                        || text.starts_with("Server reply"))
+                }
+                _ => true,
+            })
+            .skip(1)
+    }
+
+    /// Text and list items, e.g. "otherwise, an array of hashes (strings) of all pieces".
+    pub fn texts(&self) -> impl Iterator<Item = &str> {
+        self.content.iter().filter_map(|row| match &row.value {
+            MdContent::Text(text) => Some(text.as_str()),
+            MdContent::ListItem(list_item) => Some(list_item.text.as_str()),
+            _ => None,
+        })
+    }
+}
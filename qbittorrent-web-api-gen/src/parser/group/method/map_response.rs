@@ -0,0 +1,90 @@
+use case::CaseExt;
+
+use crate::{
+    md_parser,
+    types::{self, Object, RefType},
+};
+
+use super::{ApiMethod, CompositeType, CompositeTypes, ReturnType, SharedType};
+
+impl md_parser::TokenTree {
+    /// Responses which are a JSON object keyed by e.g. a category name or a torrent hash,
+    /// such as `categories` or the per torrent `downloadLimit`.
+    pub fn parse_map_response(&self, method_url: &str) -> Option<CompositeType> {
+        let example = self.json_example()?;
+        let values: Vec<_> = example.as_object()?.values().collect();
+
+        let value_type = if values.iter().all(|value| value.is_i64()) {
+            "i64".to_string()
+        } else if values.iter().all(|value| value.is_string()) {
+            "String".to_string()
+        } else if values.iter().all(|value| value.is_object()) {
+            // resolved by `link_map_object` since the object table may be documented elsewhere
+            self.map_object_name()?
+        } else {
+            return None;
+        };
+
+        Some(CompositeType::ValueResponse(types::Type::Object(Object {
+            type_info: types::TypeInfo::new(
+                method_url,
+                false,
+                false,
+                self.json_example_description(),
+            ),
+            ref_type: RefType::Map("String".into(), value_type),
+        })))
+    }
+
+    /// e.g. "Returns all categories in JSON format" -> "Categories"
+    fn map_object_name(&self) -> Option<String> {
+        let re = regex!(r"^Returns all ([\w-]+) in JSON format");
+
+        self.texts()
+            .find_map(|text| re.captures(text))
+            .map(|captures| captures[1].to_camel())
+    }
+}
+
+impl CompositeTypes {
+    fn map_object_name(&self) -> Option<&str> {
+        match self.value_response()? {
+            types::Type::Object(Object {
+                ref_type: RefType::Map(_, value),
+                ..
+            }) if !matches!(value.as_str(), "i64" | "String") => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl ApiMethod {
+    /// Shares the object table of a map response, e.g. the `Categories object` which is
+    /// documented in `sync/maindata` but returned by `torrents/categories`.
+    /// Responses without a documented object table are returned as text.
+    pub fn link_map_object(&mut self, objects: &[SharedType]) {
+        let name = match self.types.map_object_name() {
+            Some(name) => name.to_string(),
+            None => return,
+        };
+
+        if self
+            .types
+            .objects()
+            .iter()
+            .any(|object| object.name == name)
+        {
+            return;
+        }
+
+        match objects.iter().find(|object| object.name == name) {
+            Some(object) => self.types.add_shared(object.clone()),
+            None => {
+                self.types
+                    .composite_types
+                    .retain(|type_| !matches!(type_, CompositeType::ValueResponse(_)));
+                self.return_type = ReturnType::Text;
+            }
+        }
+    }
+}
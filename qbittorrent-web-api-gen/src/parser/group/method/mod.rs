@@ -1,11 +1,23 @@
+mod array_response;
 mod description;
-// mod return_type;
+mod enums;
+mod example;
+mod map_response;
+mod nested_object;
+mod reference;
+mod request_example;
+mod return_type;
+mod type_names;
 mod url;
 
-use crate::{md_parser, types};
+pub use return_type::ReturnType;
+
+use crate::{
+    error::{ParseError, ParseResult},
+    md_parser, types,
+};
 use case::CaseExt;
 use regex::Regex;
-use std::collections::BTreeMap;
 
 #[derive(Debug)]
 pub struct ApiMethod {
@@ -13,6 +25,13 @@ pub struct ApiMethod {
     pub description: Option<String>,
     pub url: String,
     pub types: CompositeTypes,
+    pub return_type: ReturnType,
+    /// The name of the response struct, e.g. `TorrentInfo`.
+    pub response_name: Option<String>,
+    /// The markdown anchor of the section, e.g. `get-torrent-list`.
+    pub anchor: Option<String>,
+    /// The header of the section in the markdown.
+    pub span: Option<md_parser::Span>,
 }
 
 #[derive(Debug)]
@@ -21,9 +40,28 @@ pub struct CompositeTypes {
 }
 
 impl CompositeTypes {
-    pub fn new(tables: &Tables) -> Self {
-        Self {
-            composite_types: tables.get_all_tables_as_types(),
+    pub fn new(tables: &Tables) -> ParseResult<Self> {
+        let mut types = Self {
+            composite_types: Vec::new(),
+        };
+        for type_ in tables.get_all_tables_as_types()? {
+            types.push_or_merge(type_);
+        }
+        types.link_nested_objects();
+
+        Ok(types)
+    }
+
+    /// Tables with the same title describe the same type, e.g. two "Possible values of `state`"
+    /// tables, so they are merged instead of generating the type twice or dropping one.
+    fn push_or_merge(&mut self, type_: CompositeType) {
+        match self
+            .composite_types
+            .iter_mut()
+            .find(|existing| existing.is_same_type(&type_))
+        {
+            Some(existing) => existing.merge(type_),
+            None => self.composite_types.push(type_),
         }
     }
 
@@ -60,6 +98,40 @@ impl CompositeTypes {
         })
     }
 
+    pub fn value_response(&self) -> Option<&types::Type> {
+        self.composite_types.iter().find_map(|type_| match type_ {
+            CompositeType::ValueResponse(p) => Some(p),
+            _ => None,
+        })
+    }
+
+    pub fn possible_fields(&self) -> Option<&TypeWithoutName> {
+        self.composite_types.iter().find_map(|type_| match type_ {
+            CompositeType::PossibleFields(p) => Some(p),
+            _ => None,
+        })
+    }
+
+    /// A response whose fields are documented as "Possible fields", e.g. `app/preferences`
+    /// which only contains the settings present in qBittorrent.ini, so every field is optional.
+    fn possible_fields_response(&self) -> Option<CompositeType> {
+        if self.response().is_some() {
+            return None;
+        }
+
+        let fields = self.possible_fields()?;
+        let types = fields.types.iter().map(types::Type::to_optional).collect();
+
+        Some(CompositeType::Response(TypeWithoutName::new(types, false)))
+    }
+
+    pub fn json_parameter(&self) -> Option<&TypeWithName> {
+        self.composite_types.iter().find_map(|type_| match type_ {
+            CompositeType::JsonParameter(p) => Some(p),
+            _ => None,
+        })
+    }
+
     pub fn objects(&self) -> Vec<&TypeWithName> {
         self.composite_types
             .iter()
@@ -79,12 +151,23 @@ impl CompositeTypes {
             })
             .collect()
     }
-}
 
-#[derive(Debug)]
-pub struct ApiParameters {
-    pub mandatory: Vec<types::Type>,
-    pub optional: Vec<types::Type>,
+    pub fn shared(&self) -> Vec<&SharedType> {
+        self.composite_types
+            .iter()
+            .filter_map(|type_| match type_ {
+                CompositeType::Shared(p) => Some(p),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Adds a type defined by another method unless the method already has it.
+    pub fn add_shared(&mut self, shared: SharedType) {
+        if !self.shared().contains(&&shared) {
+            self.composite_types.push(CompositeType::Shared(shared));
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -93,9 +176,79 @@ pub enum CompositeType {
     Object(TypeWithName),
     Response(TypeWithoutName),
     Parameters(TypeWithoutName),
+    PossibleFields(TypeWithoutName),
+    JsonParameter(TypeWithName),
+    /// A response which is a single type rather than a table of fields, e.g. `Vec<String>`.
+    ValueResponse(types::Type),
+    /// A type which is defined by another method.
+    Shared(SharedType),
 }
 
-#[derive(Debug)]
+impl CompositeType {
+    fn is_same_type(&self, other: &CompositeType) -> bool {
+        match (self, other) {
+            (CompositeType::Enum(a), CompositeType::Enum(b)) => a.name == b.name,
+            (CompositeType::Object(a), CompositeType::Object(b)) => a.name == b.name,
+            (CompositeType::Response(_), CompositeType::Response(_))
+            | (CompositeType::Parameters(_), CompositeType::Parameters(_))
+            | (CompositeType::PossibleFields(_), CompositeType::PossibleFields(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Adds the values or fields of `other` which aren't already documented.
+    fn merge(&mut self, mut other: CompositeType) {
+        if let (CompositeType::Enum(enum_), CompositeType::Enum(other)) = (&mut *self, &other) {
+            for value in &other.values {
+                if !enum_
+                    .values
+                    .iter()
+                    .any(|v| v.original_value == value.original_value)
+                {
+                    enum_.values.push(value.clone());
+                }
+            }
+        } else if let (Some(fields), Some(other_fields)) = (self.fields_mut(), other.fields_mut()) {
+            for field in other_fields.drain(..) {
+                let name = &field.get_type_info().name;
+                if !fields.iter().any(|f| &f.get_type_info().name == name) {
+                    fields.push(field);
+                }
+            }
+        }
+    }
+
+    fn fields_mut(&mut self) -> Option<&mut Vec<types::Type>> {
+        match self {
+            CompositeType::Object(TypeWithName { types, .. })
+            | CompositeType::Response(TypeWithoutName { types, .. })
+            | CompositeType::Parameters(TypeWithoutName { types, .. })
+            | CompositeType::PossibleFields(TypeWithoutName { types, .. }) => Some(types),
+            _ => None,
+        }
+    }
+}
+
+/// A type which is documented by another method and reused rather than generated again,
+/// e.g. the enums of `preferences` which are used by `setPreferences`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedType {
+    pub name: String,
+    pub group: String,
+    pub method: String,
+}
+
+impl SharedType {
+    pub fn new(name: &str, group: &str, method: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            group: group.to_string(),
+            method: method.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct TypeWithName {
     pub name: String,
     pub types: Vec<types::Type>,
@@ -122,13 +275,13 @@ impl TypeWithName {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Enum {
     pub name: String,
     pub values: Vec<EnumValue>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EnumValue {
     pub description: Option<String>,
     pub value: String,
@@ -137,31 +290,32 @@ pub struct EnumValue {
 
 impl Enum {
     fn new(name: &str, table: &md_parser::Table) -> Self {
-        let values = table.rows.iter().map(EnumValue::from).collect();
-
         Enum {
             name: name.to_string(),
-            values,
+            values: table.rows.iter().map(EnumValue::from).collect(),
         }
     }
 }
 
 impl From<&md_parser::TableRow> for EnumValue {
     fn from(row: &md_parser::TableRow) -> Self {
-        let description = row.columns.get(1).cloned();
-        let original_value = row.columns[0].clone();
-        let value = if original_value.parse::<i32>().is_ok() {
-            let name = description
-                .clone()
-                .unwrap()
-                .replace(' ', "_")
-                .replace('-', "_")
-                .replace(',', "_");
-
-            let re = Regex::new(r#"\(.*\)"#).unwrap();
-            re.replace_all(&name, "").to_camel()
-        } else {
-            original_value.to_camel()
+        EnumValue::new(&row.columns[0], row.columns.get(1).cloned())
+    }
+}
+
+impl EnumValue {
+    pub fn new(original_value: &str, description: Option<String>) -> Self {
+        let original_value = original_value.to_string();
+        let value = match (original_value.parse::<i64>(), &description) {
+            // numeric values are named after their description, e.g. `0` -> `DoNotDownload`
+            (Ok(_), Some(description)) => {
+                let re = regex!(r"\(.*\)");
+                Self::variant_name(&re.replace_all(description, ""))
+            }
+            // e.g. the file priorities of `{"type": "integer", "enum": [0, 1, 6, 7]}`
+            (Ok(number), None) if number < 0 => format!("ValueMinus{}", number.unsigned_abs()),
+            (Ok(number), None) => format!("Value{number}"),
+            (Err(_), _) => Self::variant_name(&original_value),
         };
 
         EnumValue {
@@ -170,35 +324,75 @@ impl From<&md_parser::TableRow> for EnumValue {
             original_value,
         }
     }
+
+    /// A valid identifier for the variant of a value, e.g. `stalled-uploading` ->
+    /// `StalledUploading` or `Do not download` -> `DoNotDownload`.
+    pub fn variant_name(value: &str) -> String {
+        let name = value
+            .replace(|c: char| !c.is_alphanumeric(), "_")
+            .to_camel();
+
+        match name.chars().next() {
+            Some(first) if first.is_alphabetic() => name,
+            // e.g. `2` of an enum whose other values are names
+            _ => format!("Value{name}"),
+        }
+    }
 }
 
 impl ApiMethod {
-    pub fn try_new(child: &md_parser::TokenTree) -> Option<Self> {
+    /// Returns `None` if the section doesn't describe a method, i.e. has no `Name: ...`.
+    pub fn try_new(child: &md_parser::TokenTree) -> ParseResult<Option<Self>> {
         const NAME: &str = "Name: ";
 
         child
             .find_content_starts_with(NAME)
             .map(|name| name.trim_start_matches(NAME).trim_matches('`').to_string())
             .map(|name| ApiMethod::new(child, &name))
+            .transpose()
+            .map_err(|err| child.locate(err))
     }
 
-    fn new(child: &md_parser::TokenTree, name: &str) -> Self {
-        let tables = Tables::from(child);
+    fn new(child: &md_parser::TokenTree, name: &str) -> ParseResult<Self> {
+        let tables = Tables::try_from(child)?;
         let method_description = child.parse_method_description();
-        let method_url = child.get_method_url();
-
-        ApiMethod {
+        let method_url = child.get_method_url()?;
+
+        let mut types = CompositeTypes::new(&tables)?;
+        types
+            .composite_types
+            .extend(child.parse_array_response(&method_url, &tables)?);
+        types
+            .composite_types
+            .extend(child.parse_map_response(&method_url));
+        types
+            .composite_types
+            .extend(child.parse_request_example_parameters(&types));
+        types.link_enums();
+        types
+            .composite_types
+            .extend(types.possible_fields_response());
+        let return_type = child.parse_return_type(&method_description, &types);
+        let response_name = types
+            .response()
+            .map(|response| child.response_type_name(response.is_list));
+
+        Ok(ApiMethod {
             name: name.to_string(),
             description: method_description,
             url: method_url,
-            types: CompositeTypes::new(&tables),
-        }
+            types,
+            return_type,
+            response_name,
+            anchor: child.anchor(),
+            span: child.span,
+        })
     }
 }
 
 impl md_parser::TokenTree {
-    fn find_content_starts_with(&self, starts_with: &str) -> Option<String> {
-        self.content.iter().find_map(|row| match row {
+    pub fn find_content_starts_with(&self, starts_with: &str) -> Option<String> {
+        self.content.iter().find_map(|row| match &row.value {
             md_parser::MdContent::Text(content) if content.starts_with(starts_with) => {
                 Some(content.into())
             }
@@ -207,135 +401,187 @@ impl md_parser::TokenTree {
     }
 }
 
-impl<'a> From<&'a md_parser::TokenTree> for Tables<'a> {
-    fn from(token_tree: &'a md_parser::TokenTree) -> Self {
-        let mut tables = BTreeMap::new();
+impl<'a> TryFrom<&'a md_parser::TokenTree> for Tables<'a> {
+    type Error = ParseError;
+
+    fn try_from(token_tree: &'a md_parser::TokenTree) -> ParseResult<Self> {
+        let mut tables = Vec::new();
         let mut prev_prev: Option<&md_parser::MdContent> = None;
         let mut prev: Option<&md_parser::MdContent> = None;
 
         for content in &token_tree.content {
-            if let md_parser::MdContent::Table(table) = content {
+            if let md_parser::MdContent::Table(table) = &content.value {
                 let title = match prev_prev {
                     Some(md_parser::MdContent::Text(text)) => text.clone(),
                     Some(md_parser::MdContent::Asterisk(text)) => text.clone(),
-                    _ => panic!("Expected table title, found: {:?}", prev_prev),
+                    Some(md_parser::MdContent::ListItem(list_item)) => list_item.text.clone(),
+                    _ => {
+                        return Err(token_tree.error(format!(
+                            "a title two lines above the table, found: {:?}",
+                            prev_prev
+                        )))
+                    }
                 };
 
-                tables.insert(title.replace(':', ""), table);
+                let table = md_parser::Spanned {
+                    value: table,
+                    span: content.span,
+                };
+                tables.push((title.replace(':', ""), table));
             }
 
             prev_prev = prev;
-            prev = Some(content);
+            prev = Some(&content.value);
         }
 
-        Tables { tables }
+        Ok(Tables { tables })
     }
 }
 
+/// The tables of a method in the order they are documented, keyed by their title. Titles
+/// aren't necessarily unique, e.g. an enum may be documented in two parts.
 #[derive(Debug)]
 pub struct Tables<'a> {
-    tables: BTreeMap<String, &'a md_parser::Table>,
+    tables: Vec<(String, md_parser::Spanned<&'a md_parser::Table>)>,
 }
 
-impl md_parser::Table {
+impl md_parser::Spanned<&md_parser::Table> {
     fn to_enum(&self, input_name: &str) -> Option<CompositeType> {
-        let re = Regex::new(r"^Possible values of `(\w+)`$").unwrap();
-
-        if !re.is_match(input_name) {
-            return None;
-        }
+        let re = regex!(r"^Possible values of `(\w+)`$");
 
-        Some(CompositeType::Enum(Enum::new(
-            &Self::regex_to_name(&re, input_name),
-            self,
-        )))
+        let name = Self::regex_to_name(re, input_name)?;
+        Some(CompositeType::Enum(Enum::new(&name, self.value)))
     }
 
-    fn to_object(&self, input_name: &str) -> Option<CompositeType> {
-        let re = Regex::new(r"^(\w+) object$").unwrap();
+    fn to_object(&self, input_name: &str) -> ParseResult<Option<CompositeType>> {
+        let re = regex!(r"^(\w+) object$");
 
-        if !re.is_match(input_name) {
-            return None;
-        }
+        let name = match Self::regex_to_name(re, input_name) {
+            Some(name) => name,
+            None => return Ok(None),
+        };
 
-        Some(CompositeType::Object(TypeWithName::new(
-            &Self::regex_to_name(&re, input_name),
-            self.to_types(),
-        )))
+        Ok(Some(CompositeType::Object(TypeWithName::new(
+            &name,
+            self.to_types()?,
+        ))))
     }
 
-    fn to_response(&self, input_name: &str) -> Option<CompositeType> {
-        if !input_name.starts_with("The response is a") {
-            return None;
+    fn to_response(&self, input_name: &str) -> ParseResult<Option<CompositeType>> {
+        // e.g. "- otherwise, a JSON object with the following fields" of `torrents/properties`
+        if !input_name.starts_with("The response is a")
+            && !input_name.starts_with("otherwise, a JSON")
+        {
+            return Ok(None);
         }
 
-        Some(CompositeType::Response(TypeWithoutName::new(
-            self.to_types(),
+        Ok(Some(CompositeType::Response(TypeWithoutName::new(
+            self.to_types()?,
             input_name.to_lowercase().contains("array"),
-        )))
+        ))))
     }
 
-    fn to_parameters(&self, input_name: &str) -> Option<CompositeType> {
+    fn to_parameters(&self, input_name: &str) -> ParseResult<Option<CompositeType>> {
         if !input_name.starts_with("Parameters") {
-            return None;
+            return Ok(None);
         }
 
-        Some(CompositeType::Parameters(TypeWithoutName::new(
-            self.to_types(),
+        Ok(Some(CompositeType::Parameters(TypeWithoutName::new(
+            self.to_types()?,
             input_name.to_lowercase().contains("array"),
-        )))
+        ))))
     }
 
-    fn to_composite_type(&self, input_name: &str) -> Option<CompositeType> {
-        self.to_enum(input_name)
-            .or_else(|| self.to_response(input_name))
-            .or_else(|| self.to_object(input_name))
-            .or_else(|| self.to_parameters(input_name))
+    fn to_possible_fields(&self, input_name: &str) -> ParseResult<Option<CompositeType>> {
+        if input_name != "Possible fields" {
+            return Ok(None);
+        }
+
+        Ok(Some(CompositeType::PossibleFields(TypeWithoutName::new(
+            self.to_types()?,
+            false,
+        ))))
     }
 
-    fn regex_to_name(re: &Regex, input_name: &str) -> String {
+    fn to_composite_type(&self, input_name: &str) -> ParseResult<Option<CompositeType>> {
+        if let Some(enum_) = self.to_enum(input_name) {
+            return Ok(Some(enum_));
+        }
+
+        for to_type in [
+            Self::to_response,
+            Self::to_object,
+            Self::to_parameters,
+            Self::to_possible_fields,
+        ] {
+            if let Some(type_) = to_type(self, input_name)? {
+                return Ok(Some(type_));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn regex_to_name(re: &Regex, input_name: &str) -> Option<String> {
         re.captures(input_name)
-            .unwrap()
-            .get(1)
-            .unwrap()
-            .as_str()
-            .to_string()
-            .to_camel()
+            .map(|captures| captures[1].to_camel())
+    }
+
+    fn to_types(&self) -> ParseResult<Vec<types::Type>> {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter_map(|(index, table_row)| {
+                // the rows start after the header and the separator
+                let line = self.span.line + 2 + index;
+                table_row
+                    .to_type()
+                    .map_err(|err| err.at_line(line))
+                    .transpose()
+            })
+            .collect()
     }
 }
 
 impl<'a> Tables<'a> {
-    fn get_all_tables_as_types(&self) -> Vec<CompositeType> {
+    fn get(&self, title: &str) -> Option<&'a md_parser::Table> {
         self.tables
             .iter()
-            .flat_map(|(k, v)| v.to_composite_type(k))
-            .collect()
+            .find(|(table_title, _)| table_title == title)
+            .map(|(_, table)| table.value)
     }
-}
 
-impl md_parser::Table {
-    fn to_types(&self) -> Vec<types::Type> {
-        self.rows
+    fn get_all_tables_as_types(&self) -> ParseResult<Vec<CompositeType>> {
+        let types = self
+            .tables
             .iter()
-            .flat_map(|table_row| table_row.to_type())
-            .collect()
+            .map(|(k, v)| v.to_composite_type(k))
+            .collect::<ParseResult<Vec<_>>>()?;
+
+        Ok(types.into_iter().flatten().collect())
     }
 }
 
 impl md_parser::TableRow {
-    fn to_type(&self) -> Option<types::Type> {
+    fn to_type(&self) -> ParseResult<Option<types::Type>> {
         let columns = &self.columns;
+        let type_ = columns.get(1).ok_or_else(|| {
+            ParseError::new(format!(
+                "a type in the second column of the row `{}`",
+                columns.join(" | ")
+            ))
+        })?;
         let description = columns.get(2).cloned();
 
-        match &columns.get(2) {
+        Ok(match &columns.get(2) {
             // If the description contains a default value it means that the parameter is optional.
             Some(desc) if desc.contains("default: ") => {
                 // type defines a variable as default if it contains: _optional_
                 let name_with_optional = format!("{} {}", columns[0], types::OPTIONAL);
-                types::Type::from(&columns[1], &name_with_optional, description)
+                types::Type::from(type_, &name_with_optional, description)
             }
-            _ => types::Type::from(&columns[1], &columns[0], description),
-        }
+            _ => types::Type::from(type_, &columns[0], description),
+        })
     }
 }
 
@@ -359,8 +605,8 @@ mod tests {
             let input = include_str!(concat!(TEST_DIR!(), "/", $test_file, ".md"));
 
             // when
-            let tree = TokenTreeFactory::create(input);
-            let api_method = ApiMethod::try_new(&tree.children[0]).unwrap();
+            let tree = TokenTreeFactory::create(input).unwrap();
+            let api_method = ApiMethod::try_new(&tree.children[0]).unwrap().unwrap();
 
             // then
             let api_method_as_str = format!("{api_method:#?}");
@@ -377,8 +623,8 @@ mod tests {
             use std::path::Path;
 
             let input = include_str!(concat!(TEST_DIR!(), "/", $test_file, ".md"));
-            let tree = TokenTreeFactory::create(input);
-            let api_method = ApiMethod::try_new(&tree.children[0]).unwrap();
+            let tree = TokenTreeFactory::create(input).unwrap();
+            let api_method = ApiMethod::try_new(&tree.children[0]).unwrap().unwrap();
 
             let tree_as_str = format!("{tree:#?}");
             let api_method_as_str = format!("{api_method:#?}");
@@ -403,6 +649,11 @@ mod tests {
         };
     }
 
+    #[test]
+    fn nested_object_tables() {
+        run_test!("nested_object_tables");
+    }
+
     #[test]
     fn search_result() {
         run_test!("search_result");
@@ -427,4 +678,44 @@ mod tests {
     fn ref_type() {
         run_test!("ref_type");
     }
+
+    #[test]
+    fn possible_fields() {
+        run_test!("possible_fields");
+    }
+
+    #[test]
+    fn parameter_enum() {
+        run_test!("parameter_enum");
+    }
+
+    #[test]
+    fn bool_return() {
+        run_test!("bool_return");
+    }
+
+    #[test]
+    fn array_of_enum() {
+        run_test!("array_of_enum");
+    }
+
+    #[test]
+    fn array_of_strings() {
+        run_test!("array_of_strings");
+    }
+
+    #[test]
+    fn map_of_integers() {
+        run_test!("map_of_integers");
+    }
+
+    #[test]
+    fn map_of_objects() {
+        run_test!("map_of_objects");
+    }
+
+    #[test]
+    fn enum_identifiers() {
+        run_test!("enum_identifiers");
+    }
 }
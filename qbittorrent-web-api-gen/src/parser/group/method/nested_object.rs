@@ -0,0 +1,68 @@
+use case::CaseExt;
+
+use crate::types::{self, Object, RefType};
+
+use super::CompositeTypes;
+
+impl CompositeTypes {
+    /// Attaches the object tables of a method to the fields they describe when the field
+    /// doesn't name the object itself, e.g. a `server_state` field of type `object` followed
+    /// by a "ServerState object" table.
+    pub fn link_nested_objects(&mut self) {
+        let objects: Vec<String> = self
+            .objects()
+            .into_iter()
+            .map(|object| object.name.clone())
+            .collect();
+
+        for composite_type in &mut self.composite_types {
+            let fields = match composite_type.fields_mut() {
+                Some(fields) => fields,
+                None => continue,
+            };
+
+            for field in fields.iter_mut() {
+                if let Some(object) = nested_object(field, &objects) {
+                    *field = field.with_object(&object);
+                }
+            }
+        }
+    }
+}
+
+/// The object table named after the field, e.g. `ServerState` for `server_state` or `Result`
+/// for `results`, unless the field already refers to one of the objects of the method.
+fn nested_object(field: &types::Type, objects: &[String]) -> Option<String> {
+    match field {
+        types::Type::JsonObject(_) => {}
+        types::Type::Object(Object {
+            ref_type: RefType::String(name) | RefType::Map(_, name),
+            ..
+        }) if !objects.contains(name) => {}
+        _ => return None,
+    }
+
+    let name = field.get_type_info().name.to_camel();
+    let singular = name.trim_end_matches('s').to_string();
+
+    [name, singular]
+        .into_iter()
+        .find(|candidate| objects.contains(candidate))
+}
+
+impl types::Type {
+    fn with_object(&self, object: &str) -> types::Type {
+        let ref_type = match self {
+            types::Type::Object(Object {
+                ref_type: RefType::Map(key, _),
+                ..
+            }) => RefType::Map(key.clone(), object.to_string()),
+            _ => RefType::String(object.to_string()),
+        };
+
+        types::Type::Object(Object {
+            type_info: self.get_type_info().clone(),
+            ref_type,
+        })
+    }
+}
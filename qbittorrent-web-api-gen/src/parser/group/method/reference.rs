@@ -0,0 +1,107 @@
+use crate::types::{self, Object, RefType};
+
+use super::{ApiMethod, CompositeType, SharedType};
+
+impl ApiMethod {
+    /// Fields which are documented as the response of another method, e.g. `torrents` of
+    /// `sync/maindata`: "value: same as [torrent list](#get-torrent-list), map from string
+    /// to torrents object". Returns the name of each field and the anchor it refers to.
+    pub fn references(&self) -> Vec<(String, String)> {
+        let re = regex!(r"same as \[[^\]]+\]\(#([\w-]+)\)");
+
+        self.types
+            .composite_types
+            .iter()
+            .flat_map(|type_| match type_ {
+                CompositeType::Response(p) => p.types.iter().collect(),
+                CompositeType::Object(p) => p.types.iter().collect(),
+                _ => vec![],
+            })
+            .filter_map(|field| {
+                let type_info = field.get_type_info();
+                let captures = re.captures(type_info.description.as_ref()?)?;
+
+                Some((type_info.name.clone(), captures[1].to_string()))
+            })
+            .collect()
+    }
+
+    /// Reuses the response of the referenced method for the field. If the method documents
+    /// the referenced values itself, e.g. the `Torrents object` of `sync/maindata` where every
+    /// field is optional since only changed values are returned, the object is kept but its
+    /// fields use the types of the response, e.g. `TorrentState` for `state`.
+    pub fn link_reference(
+        &mut self,
+        field_name: &str,
+        response: &SharedType,
+        response_fields: &[types::Type],
+    ) {
+        let object_name = match self.field_mut(field_name) {
+            Some(types::Type::Object(Object {
+                ref_type: RefType::String(name) | RefType::Map(_, name),
+                ..
+            })) => Some(name.clone()),
+            Some(_) => None,
+            None => return,
+        };
+
+        let local_object = self
+            .types
+            .composite_types
+            .iter_mut()
+            .find_map(|type_| match type_ {
+                CompositeType::Object(object) if Some(&object.name) == object_name.as_ref() => {
+                    Some(object)
+                }
+                _ => None,
+            });
+
+        if let Some(object) = local_object {
+            let mut enums = vec![];
+            for field in object.types.iter_mut() {
+                let name = &field.get_type_info().name;
+                if let Some(types::Type::Enum(enum_ref)) = response_fields
+                    .iter()
+                    .find(|response_field| &response_field.get_type_info().name == name)
+                {
+                    *field = field.to_enum(&enum_ref.name);
+                    enums.push(SharedType::new(
+                        &enum_ref.name,
+                        &response.group,
+                        &response.method,
+                    ));
+                }
+            }
+
+            for enum_ in enums {
+                self.types.add_shared(enum_);
+            }
+        } else if let Some(field) = self.field_mut(field_name) {
+            let ref_type = match field {
+                types::Type::Object(Object {
+                    ref_type: RefType::Map(key, _),
+                    ..
+                }) => RefType::Map(key.clone(), response.name.clone()),
+                _ => RefType::String(response.name.clone()),
+            };
+
+            *field = types::Type::Object(Object {
+                type_info: field.get_type_info().clone(),
+                ref_type,
+            });
+            self.types.add_shared(response.clone());
+        }
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut types::Type> {
+        self.types
+            .composite_types
+            .iter_mut()
+            .flat_map(|type_| match type_ {
+                CompositeType::Response(p) => p.types.iter_mut().collect(),
+                CompositeType::Object(p) => p.types.iter_mut().collect(),
+                _ => vec![],
+            })
+            .find(|field| field.get_type_info().name == name)
+    }
+}
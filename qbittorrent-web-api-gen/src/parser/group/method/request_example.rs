@@ -0,0 +1,93 @@
+use crate::{
+    md_parser::{self, MdContent},
+    types,
+};
+
+use super::{CompositeType, CompositeTypes, TypeWithoutName};
+
+impl md_parser::TokenTree {
+    /// Parameters of methods which are only documented by an example request instead of a
+    /// parameter table, e.g. `torrents/downloadLimit`:
+    ///
+    /// ```http
+    /// POST /api/v2/torrents/downloadLimit HTTP/1.1
+    /// ...
+    ///
+    /// hashes=8c212779b4abde7c6bc608063a0d008b7e40ce32|284b83c9c7935002391129fd97f43db5d7cc2ba0
+    /// ```
+    ///
+    /// The type is derived from the example value and the description from the line starting
+    /// with the parameter, e.g. "`hashes` can contain multiple hashes separated by `|`".
+    pub fn parse_request_example_parameters(
+        &self,
+        types: &CompositeTypes,
+    ) -> Option<CompositeType> {
+        if types.has_parameters() {
+            return None;
+        }
+
+        let body = self.content.iter().find_map(|row| match &row.value {
+            MdContent::CodeBlock(code_block)
+                if code_block.language.as_deref() == Some("http")
+                    && code_block.code.starts_with("POST ") =>
+            {
+                code_block
+                    .code
+                    .split_once("\n\n")
+                    .map(|(_, body)| body.trim())
+            }
+            _ => None,
+        })?;
+
+        let parameters: Vec<types::Type> = body
+            // e.g. `hashes=...?value=true` of `torrents/setForceStart`
+            .split(['&', '?'])
+            .filter_map(|pair| pair.split_once('='))
+            .filter_map(|(name, value)| self.request_example_parameter(name, value))
+            .collect();
+
+        if parameters.is_empty() {
+            None
+        } else {
+            Some(CompositeType::Parameters(TypeWithoutName::new(
+                parameters, false,
+            )))
+        }
+    }
+
+    fn request_example_parameter(&self, name: &str, value: &str) -> Option<types::Type> {
+        let type_ = if value == "true" || value == "false" {
+            "bool"
+        } else if value.parse::<i64>().is_ok() {
+            "integer"
+        } else if value.parse::<f64>().is_ok() {
+            "float"
+        } else {
+            "string"
+        };
+
+        let prefix = format!("`{name}`");
+        let description = self
+            .texts()
+            .flat_map(str::lines)
+            .find(|line| line.starts_with(&prefix))
+            .map(str::to_string);
+
+        match &description {
+            // e.g. "`enable` is a boolean, affects the torrents listed in `hashes`, default is `false`"
+            Some(desc) if desc.contains("default is ") => {
+                let name_with_optional = format!("{} {}", name, types::OPTIONAL);
+                types::Type::from(type_, &name_with_optional, description)
+            }
+            _ => types::Type::from(type_, name, description),
+        }
+    }
+}
+
+impl CompositeTypes {
+    fn has_parameters(&self) -> bool {
+        self.composite_types
+            .iter()
+            .any(|type_| matches!(type_, CompositeType::Parameters(_)))
+    }
+}
@@ -1,107 +1,64 @@
-use std::collections::HashMap;
+use crate::md_parser::{self, MdContent};
 
-use crate::{
-    md_parser,
-    parser::{types, ReturnTypeParameter},
-};
+use super::CompositeTypes;
 
-use super::Tables;
-
-#[derive(Debug)]
-pub struct ReturnType {
-    pub is_list: bool,
-    pub parameters: Vec<ReturnTypeParameter>,
-}
-
-impl md_parser::Table {
-    fn to_return_type_parameters(
-        &self,
-        types: &HashMap<String, types::TypeDescription>,
-    ) -> Vec<ReturnTypeParameter> {
-        self.rows
-            .iter()
-            .map(|parameter| parameter.to_return_type_parameter(types))
-            .collect()
-    }
+/// What a method returns, derived from the response table or the text following "Returns:".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnType {
+    /// A JSON response described by a response table or a single type.
+    Json,
+    /// Nothing besides "Ok." or "Fails.", e.g. `torrents/pause`.
+    Unit,
+    /// e.g. `app/version` which returns `v4.1.3`.
+    Version,
+    /// e.g. `transfer/downloadLimit`.
+    Integer,
+    /// `1` or `0`, e.g. `transfer/speedLimitsMode`.
+    Bool,
+    /// A response which is returned as is, e.g. `app/defaultSavePath` or undocumented JSON.
+    Text,
 }
 
 impl md_parser::TokenTree {
-    pub fn parse_return_type(&self) -> Option<ReturnType> {
-        let tables: Tables = self.into();
-        let table = tables
-            .get_type_containing_as_table("The response is a")
-            // these two are special cases not following a pattern
-            .or_else(|| tables.get_type_containing_as_table("Possible fields"))
-            .or_else(|| {
-                tables.get_type_containing_as_table(
-                    "Each element of the array has the following properties",
-                )
-            })?;
-
-        let types = self.parse_object_types();
-
-        Some(ReturnType {
-            parameters: table.to_return_type_parameters(&types),
-            is_list: self.is_list(),
-        })
-    }
-
-    fn is_list(&self) -> bool {
-        self.find_content_starts_with("The response is a")
-            .map(|found| found.contains("array"))
-            .unwrap_or_else(|| false)
-    }
-
-    pub fn parse_object_types(&self) -> HashMap<String, types::TypeDescription> {
-        let tables: Tables = self.into();
-        const POSSIBLE_VALUES_OF: &str = "Possible values of ";
-
-        tables
-            .get_all_type_containing_as_table(POSSIBLE_VALUES_OF)
-            .iter()
-            .map(|(k, table)| {
-                let name = k
-                    .trim_start_matches(POSSIBLE_VALUES_OF)
-                    .replace('`', "")
-                    .replace(':', "");
-
-                (name, table.to_type_description())
-            })
-            .collect()
-    }
-}
+    pub fn parse_return_type(
+        &self,
+        description: &Option<String>,
+        types: &CompositeTypes,
+    ) -> ReturnType {
+        if types.response().is_some() || types.value_response().is_some() {
+            return ReturnType::Json;
+        }
 
-impl md_parser::Table {
-    pub fn to_type_description(&self) -> types::TypeDescription {
-        types::TypeDescription {
-            values: self.to_type_descriptions(),
+        match description {
+            Some(description) if description.starts_with("The response") => {
+                ReturnType::from_description(description)
+            }
+            _ if self.has_undocumented_response() => ReturnType::Text,
+            _ => ReturnType::Unit,
         }
     }
 
-    pub fn to_type_descriptions(&self) -> Vec<types::TypeDescriptions> {
-        self.rows
-            .iter()
-            .map(|row| types::TypeDescriptions {
-                value: row.columns[0].to_string(),
-                description: row.columns[1].to_string(),
-            })
-            .collect()
+    /// e.g. "Returns all categories in JSON format" or "Server reply (example):"
+    fn has_undocumented_response(&self) -> bool {
+        self.content.iter().any(|row| match &row.value {
+            MdContent::Text(text) => {
+                text.starts_with("Returns ") || text.starts_with("Server reply")
+            }
+            _ => false,
+        })
     }
 }
 
-impl md_parser::TableRow {
-    fn to_return_type_parameter(
-        &self,
-        type_map: &HashMap<String, types::TypeDescription>,
-    ) -> ReturnTypeParameter {
-        let columns = &self.columns;
-
-        ReturnTypeParameter {
-            name: columns[0].clone(),
-            description: columns[2].clone(),
-            return_type: self
-                .to_types_with_types(type_map)
-                .unwrap_or_else(|| panic!("Failed to parse type {}", &columns[1])),
+impl ReturnType {
+    fn from_description(description: &str) -> Self {
+        if description.starts_with("The response is a string") && description.contains("version") {
+            ReturnType::Version
+        } else if description.starts_with("The response is `1` if") {
+            ReturnType::Bool
+        } else if description.starts_with("The response is the value of") {
+            ReturnType::Integer
+        } else {
+            ReturnType::Text
         }
     }
 }
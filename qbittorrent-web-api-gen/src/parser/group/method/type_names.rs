@@ -0,0 +1,133 @@
+use case::CaseExt;
+
+use crate::{
+    md_parser,
+    types::{self, Object, RefType},
+};
+
+use super::{ApiMethod, CompositeType, CompositeTypes};
+
+const RESPONSE: &str = "Response";
+
+/// Names which are either not descriptive when derived from the section title or which
+/// would collide with a different type in the flat `types` module, keyed by the section
+/// title of the method.
+const TYPE_NAME_OVERRIDES: &[(&str, &str, &str)] = &[
+    ("Get application preferences", RESPONSE, "Preferences"),
+    ("Get torrent list", RESPONSE, "TorrentInfo"),
+    ("Get torrent list", "State", "TorrentState"),
+    ("Get torrent list", "Filter", "TorrentFilter"),
+    ("Get torrent list", "Sort", "TorrentSort"),
+    (
+        "Get torrent generic properties",
+        RESPONSE,
+        "TorrentProperties",
+    ),
+    ("Get torrent trackers", "Status", "TrackerStatus"),
+    ("Get torrent contents", "Priority", "FilePriority"),
+    ("Set file priority", "Priority", "FilePriority"),
+    ("Start search", RESPONSE, "SearchJob"),
+    ("Get search status", "Status", "SearchJobStatus"),
+    ("Get search results", "Status", "SearchJobStatus"),
+    ("Get search results", "Result", "SearchResult"),
+    ("Get search plugins", "Category", "SearchPluginCategory"),
+];
+
+fn type_name_override(title: &str, name: &str) -> Option<&'static str> {
+    TYPE_NAME_OVERRIDES
+        .iter()
+        .find(|(t, n, _)| *t == title && *n == name)
+        .map(|(_, _, type_name)| *type_name)
+}
+
+impl md_parser::TokenTree {
+    /// The name of the response type derived from the section title, e.g.
+    /// "Get build info" -> "BuildInfo" or, for arrays, "Get torrent trackers" -> "TorrentTracker".
+    pub fn response_type_name(&self, is_list: bool) -> String {
+        let title = self.title.clone().unwrap_or_default();
+        if let Some(type_name) = type_name_override(&title, RESPONSE) {
+            return type_name.to_string();
+        }
+
+        let name = title
+            .trim_start_matches("Get ")
+            .replace(['\'', '/', '-'], " ")
+            .replace(' ', "_")
+            .to_camel();
+
+        // the title describes all elements while the type describes one of them
+        if is_list && name.ends_with('s') && !name.ends_with("ss") && !name.ends_with("us") {
+            name.trim_end_matches('s').to_string()
+        } else {
+            name
+        }
+    }
+}
+
+impl ApiMethod {
+    /// Renames the types of the method which are listed in `TYPE_NAME_OVERRIDES`.
+    pub fn apply_type_name_overrides(&mut self, title: &str) {
+        for (_, name, type_name) in TYPE_NAME_OVERRIDES
+            .iter()
+            .filter(|(t, n, _)| *t == title && *n != RESPONSE)
+        {
+            self.types.rename_type(name, type_name);
+        }
+    }
+}
+
+impl CompositeTypes {
+    /// Renames an enum or object and all fields and shared types referring to it.
+    fn rename_type(&mut self, from: &str, to: &str) {
+        for composite_type in &mut self.composite_types {
+            let fields = match composite_type {
+                CompositeType::Enum(enum_) => {
+                    if enum_.name == from {
+                        enum_.name = to.to_string();
+                    }
+                    continue;
+                }
+                CompositeType::Object(object) | CompositeType::JsonParameter(object) => {
+                    if object.name == from {
+                        object.name = to.to_string();
+                    }
+                    &mut object.types
+                }
+                CompositeType::Response(p)
+                | CompositeType::Parameters(p)
+                | CompositeType::PossibleFields(p) => &mut p.types,
+                CompositeType::ValueResponse(value) => {
+                    value.rename_reference(from, to);
+                    continue;
+                }
+                CompositeType::Shared(shared) => {
+                    if shared.name == from {
+                        shared.name = to.to_string();
+                    }
+                    continue;
+                }
+            };
+
+            for field in fields {
+                field.rename_reference(from, to);
+            }
+        }
+    }
+}
+
+impl types::Type {
+    fn rename_reference(&mut self, from: &str, to: &str) {
+        match self {
+            types::Type::Enum(enum_ref) if enum_ref.name == from => {
+                enum_ref.name = to.to_string();
+            }
+            types::Type::Object(Object {
+                ref_type: RefType::String(name) | RefType::Map(_, name),
+                ..
+            }) if name == from => {
+                *name = to.to_string();
+            }
+            _ => {}
+        }
+    }
+}
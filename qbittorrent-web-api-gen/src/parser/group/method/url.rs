@@ -1,11 +1,11 @@
-use crate::md_parser;
+use crate::{error::ParseResult, md_parser};
 
 impl md_parser::TokenTree {
-    pub fn get_method_url(&self) -> String {
+    pub fn get_method_url(&self) -> ParseResult<String> {
         const START: &str = "Name: ";
 
         self.find_content_starts_with(START)
             .map(|text| text.trim_start_matches(START).trim_matches('`').to_string())
-            .expect("Could find method url")
+            .ok_or_else(|| self.error("the name of the method, e.g. `Name: info`"))
     }
 }
@@ -1,8 +1,10 @@
 mod description;
+mod json_parameter;
 mod method;
+mod referenced_enum;
 mod url;
 
-use crate::md_parser;
+use crate::{error::ParseResult, md_parser};
 
 pub use method::*;
 
@@ -12,31 +14,79 @@ pub struct ApiGroup {
     pub methods: Vec<ApiMethod>,
     pub description: Option<String>,
     pub url: String,
+    /// The header of the group in the markdown.
+    pub span: Option<md_parser::Span>,
 }
 
 impl ApiGroup {
-    pub fn new(tree: &md_parser::TokenTree) -> ApiGroup {
-        ApiGroup {
-            name: tree.name(),
-            methods: tree.methods(),
+    pub fn new(tree: &md_parser::TokenTree) -> ParseResult<ApiGroup> {
+        Ok(ApiGroup {
+            name: tree.name()?,
+            methods: tree.methods()?,
             description: tree.parse_group_description(),
-            url: tree.get_group_url(),
-        }
+            url: tree.get_group_url()?,
+            span: tree.span,
+        })
     }
 }
 
+/// The name of a group from its title, e.g. "Torrent management" -> "torrent_management".
+pub fn group_name(title: &str) -> String {
+    title
+        .to_lowercase()
+        .trim_end_matches("(experimental)")
+        .trim()
+        .replace(' ', "_")
+}
+
 impl md_parser::TokenTree {
-    fn name(&self) -> String {
-        self.title
-            .clone()
-            .unwrap()
-            .to_lowercase()
-            .trim_end_matches("(experimental)")
-            .trim()
-            .replace(' ', "_")
+    fn name(&self) -> ParseResult<String> {
+        let title = self
+            .title
+            .as_ref()
+            .ok_or_else(|| self.error("a title for the group"))?;
+
+        Ok(group_name(title))
+    }
+
+    fn methods(&self) -> ParseResult<Vec<ApiMethod>> {
+        let mut methods = vec![];
+        for child in &self.children {
+            let mut method = match ApiMethod::try_new(child)? {
+                Some(method) => method,
+                None => continue,
+            };
+            method
+                .types
+                .composite_types
+                .extend(self.parse_json_parameter(child)?);
+            self.link_referenced_enums(&mut method)?;
+            if let Some(title) = &child.title {
+                method.apply_type_name_overrides(title);
+            }
+
+            methods.push(method);
+        }
+
+        Ok(methods)
+    }
+
+    fn find_child_by_anchor(&self, anchor: &str) -> Option<&md_parser::TokenTree> {
+        self.children
+            .iter()
+            .find(|child| child.anchor().as_deref() == Some(anchor))
     }
 
-    fn methods(&self) -> Vec<ApiMethod> {
-        self.children.iter().flat_map(ApiMethod::try_new).collect()
+    /// The markdown anchor of the title, e.g. "Get application preferences" ->
+    /// "get-application-preferences".
+    fn anchor(&self) -> Option<String> {
+        self.title.as_ref().map(|title| {
+            title
+                .to_lowercase()
+                .chars()
+                .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
+                .collect::<String>()
+                .replace(' ', "-")
+        })
     }
 }
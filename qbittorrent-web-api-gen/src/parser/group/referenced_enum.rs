@@ -0,0 +1,51 @@
+use case::CaseExt;
+
+use crate::{error::ParseResult, md_parser};
+
+use super::{ApiMethod, SharedType};
+
+impl md_parser::TokenTree {
+    /// Parameters can refer to another method for their possible values, e.g.
+    /// "File priority to set (consult [torrent contents API](#get-torrent-contents) for possible values)".
+    pub fn link_referenced_enums(&self, method: &mut ApiMethod) -> ParseResult<()> {
+        let re = regex!(r"\]\(#([\w-]+)\) for possible values");
+
+        let references: Vec<(String, String)> = method
+            .types
+            .parameters()
+            .iter()
+            .filter_map(|param| {
+                let type_info = param.get_type_info();
+                let cap = re.captures(type_info.description.as_ref()?)?;
+
+                Some((type_info.name.clone(), cap[1].to_string()))
+            })
+            .collect();
+
+        for (parameter_name, anchor) in references {
+            let referenced = match self
+                .find_child_by_anchor(&anchor)
+                .map(ApiMethod::try_new)
+                .transpose()?
+                .flatten()
+            {
+                Some(referenced) => referenced,
+                None => continue,
+            };
+
+            let group_name = self.name()?;
+            let enum_ = referenced
+                .types
+                .enums()
+                .into_iter()
+                .find(|e| e.name == parameter_name.to_camel())
+                .map(|e| SharedType::new(&e.name, &group_name, &referenced.name));
+
+            if let Some(enum_) = enum_ {
+                method.types.link_parameter_enum(&parameter_name, enum_);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,22 +1,22 @@
-use regex::Regex;
-
-use crate::md_parser;
+use crate::{error::ParseResult, md_parser};
 
 impl md_parser::TokenTree {
-    pub fn get_group_url(&self) -> String {
+    pub fn get_group_url(&self) -> ParseResult<String> {
+        const EXPECTED: &str =
+            r#"the url of the group, e.g. `All Application API methods are under "app", e.g.`"#;
+
         let row = self
             .find_content_contains("API methods are under")
-            .expect("Could not find api method");
+            .ok_or_else(|| self.error(EXPECTED))?;
 
-        let re = Regex::new(r#"All (?:\w+\s?)+ API methods are under "(\w+)", e.g."#)
-            .expect("Failed to create regex");
+        let re = regex!(r#"All (?:\w+\s?)+ API methods are under "(\w+)", e.g."#);
 
-        let res = re.captures(&row).expect("Failed find capture");
-        res[1].to_string()
+        let res = re.captures(&row).ok_or_else(|| self.error(EXPECTED))?;
+        Ok(res[1].to_string())
     }
 
     fn find_content_contains(&self, contains: &str) -> Option<String> {
-        self.content.iter().find_map(|row| match row {
+        self.content.iter().find_map(|row| match &row.value {
             md_parser::MdContent::Text(content) if content.contains(contains) => {
                 Some(content.into())
             }
@@ -1,22 +1,78 @@
-use crate::{md_parser, types};
+use crate::{error::ParseResult, md_parser, types};
 
 mod group;
 
-#[derive(Debug)]
-pub struct ReturnTypeParameter {
-    pub name: String,
-    pub description: String,
-    pub return_type: types::Type,
-}
-
 pub use group::*;
 
-pub fn parse_api_groups(token_tree: md_parser::TokenTree) -> Vec<ApiGroup> {
+pub fn parse_api_groups(token_tree: md_parser::TokenTree) -> ParseResult<Vec<ApiGroup>> {
     parse_groups(extract_relevant_parts(token_tree))
 }
 
-pub fn parse_groups(trees: Vec<md_parser::TokenTree>) -> Vec<ApiGroup> {
-    trees.iter().map(ApiGroup::new).collect()
+pub fn parse_groups(trees: Vec<md_parser::TokenTree>) -> ParseResult<Vec<ApiGroup>> {
+    let mut groups: Vec<ApiGroup> = trees
+        .iter()
+        .map(ApiGroup::new)
+        .collect::<ParseResult<_>>()?;
+
+    let objects: Vec<SharedType> = groups
+        .iter()
+        .flat_map(|group| {
+            group.methods.iter().flat_map(|method| {
+                method
+                    .types
+                    .objects()
+                    .into_iter()
+                    .map(|object| SharedType::new(&object.name, &group.name, &method.name))
+            })
+        })
+        .collect();
+
+    groups
+        .iter_mut()
+        .flat_map(|group| &mut group.methods)
+        .for_each(|method| method.link_map_object(&objects));
+
+    link_references(&mut groups);
+
+    Ok(groups)
+}
+
+/// Links fields which refer to another method, e.g. "same as [torrent list](#get-torrent-list)",
+/// to the response of that method.
+fn link_references(groups: &mut [ApiGroup]) {
+    let responses: Vec<(String, SharedType, Vec<types::Type>)> = groups
+        .iter()
+        .flat_map(|group| {
+            group.methods.iter().filter_map(|method| {
+                Some((
+                    method.anchor.clone()?,
+                    SharedType::new(method.response_name.as_ref()?, &group.name, &method.name),
+                    method.types.response()?.types.clone(),
+                ))
+            })
+        })
+        .collect();
+
+    for method in groups.iter_mut().flat_map(|group| &mut group.methods) {
+        for (field_name, anchor) in method.references() {
+            if let Some((_, response, fields)) = responses.iter().find(|(a, ..)| *a == anchor) {
+                method.link_reference(&field_name, response, fields);
+            }
+        }
+    }
+}
+
+/// The version of the documented api, e.g. `2.8.3` from the last "API v2.8.3" of the changelog.
+pub fn parse_api_version(token_tree: &md_parser::TokenTree) -> Option<String> {
+    token_tree
+        .children
+        .iter()
+        .find(|child| child.title.as_deref() == Some("Changes"))?
+        .children
+        .iter()
+        .rev()
+        .find_map(|change| change.title.as_deref()?.strip_prefix("API v"))
+        .map(str::to_string)
 }
 
 fn extract_relevant_parts(tree: md_parser::TokenTree) -> Vec<md_parser::TokenTree> {
@@ -43,7 +99,7 @@ mod tests {
 
     fn parse() -> md_parser::TokenTree {
         let content = include_str!("../../api-4_1.md");
-        let md_tree = md_parser::TokenTreeFactory::create(content);
+        let md_tree = md_parser::TokenTreeFactory::create(content).unwrap();
 
         let output = format!("{:#?}", md_tree);
         fs::write("token_tree.txt", output).unwrap();
@@ -53,9 +109,236 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let groups = parse_groups(extract_relevant_parts(parse()));
+        let groups = parse_groups(extract_relevant_parts(parse())).unwrap();
 
         let groups_as_str = format!("{:#?}", groups);
         fs::write("groups.txt", groups_as_str).unwrap();
     }
+
+    #[test]
+    fn types_with_the_same_name_are_identical() {
+        assert_types_with_the_same_name_are_identical(parse());
+    }
+
+    #[test]
+    #[cfg(feature = "commonmark")]
+    fn types_with_the_same_name_are_identical_with_commonmark() {
+        let content = include_str!("../../api-4_1.md");
+        let tree = md_parser::TokenTreeFactory::create_commonmark(content).unwrap();
+
+        assert_types_with_the_same_name_are_identical(tree);
+    }
+
+    #[test]
+    #[cfg(feature = "commonmark")]
+    fn both_tokenizers_parse_the_same_groups() {
+        let content = include_str!("../../api-4_1.md");
+        let commonmark_tree = md_parser::TokenTreeFactory::create_commonmark(content).unwrap();
+
+        let groups = parse_groups(extract_relevant_parts(parse())).unwrap();
+        let commonmark_groups = parse_groups(extract_relevant_parts(commonmark_tree)).unwrap();
+
+        pretty_assertions::assert_eq!(format!("{groups:#?}"), format!("{commonmark_groups:#?}"));
+    }
+
+    fn assert_types_with_the_same_name_are_identical(tree: md_parser::TokenTree) {
+        let groups = parse_groups(extract_relevant_parts(tree)).unwrap();
+        let mut types: std::collections::HashMap<String, String> = Default::default();
+
+        for method in groups.iter().flat_map(|group| &group.methods) {
+            let objects = method
+                .types
+                .objects()
+                .into_iter()
+                .map(|obj| (obj.name.clone(), format!("{:?}", obj.types)));
+            let enums = method
+                .types
+                .enums()
+                .into_iter()
+                .map(|e| (e.name.clone(), format!("{:?}", e.values)));
+
+            for (name, definition) in objects.chain(enums) {
+                let existing = types
+                    .entry(name.clone())
+                    .or_insert_with(|| definition.clone());
+                assert_eq!(existing, &definition, "{name} is defined differently");
+            }
+        }
+    }
+
+    #[test]
+    fn field_references_reuse_the_response_of_the_referenced_method() {
+        let content = r#"
+# Torrent management #
+
+All Torrent management API methods are under "torrents", e.g.: `/api/v2/torrents/methodName`.
+
+## Get torrent list ##
+
+Name: `info`
+
+The response is a JSON array with the following fields
+
+Property    | Type    | Description
+------------|---------|------------
+`hash`      | string  | Torrent hash
+
+# Sync #
+
+All Sync API methods are under "sync", e.g.: `/api/v2/sync/methodName`.
+
+## Get main data ##
+
+Name: `maindata`
+
+The response is a JSON object with the following possible fields
+
+Property    | Type    | Description
+------------|---------|------------
+`torrents`  | object  | Property: torrent hash, value: same as [torrent list](#get-torrent-list)
+"#;
+        let tree = md_parser::TokenTreeFactory::create(content).unwrap();
+
+        let groups = parse_groups(tree.children).unwrap();
+
+        let maindata = &groups[1].methods[0];
+        let torrents = &maindata.types.response().unwrap().types[0];
+        assert!(matches!(
+            torrents,
+            types::Type::Object(types::Object { ref_type: types::RefType::String(name), .. })
+                if name == "TorrentInfo"
+        ));
+        assert_eq!(
+            maindata.types.shared(),
+            vec![&SharedType::new(
+                "TorrentInfo",
+                "torrent_management",
+                "info"
+            )]
+        );
+    }
+
+    #[test]
+    fn documented_partial_objects_keep_their_optional_fields() {
+        let content = r#"
+# Torrent management #
+
+All Torrent management API methods are under "torrents", e.g.: `/api/v2/torrents/methodName`.
+
+## Get torrent list ##
+
+Name: `info`
+
+The response is a JSON array with the following fields
+
+Property    | Type    | Description
+------------|---------|------------
+`hash`      | string  | Torrent hash
+`state`     | string  | Torrent state. See table here below for the possible values
+
+Possible values of `state`:
+
+Value         | Description
+--------------|------------
+`error`       | Some error occurred, applies to paused torrents
+`pausedUP`    | Torrent is paused and has finished downloading
+
+# Sync #
+
+All Sync API methods are under "sync", e.g.: `/api/v2/sync/methodName`.
+
+## Get main data ##
+
+Name: `maindata`
+
+The response is a JSON object with the following possible fields
+
+Property               | Type    | Description
+-----------------------|---------|------------
+`torrents`_optional_   | object  | Property: torrent hash, value: same as [torrent list](#get-torrent-list), map from string to torrents object
+
+**Torrents object:**
+
+Property               | Type    | Description
+-----------------------|---------|------------
+`hash`_optional_       | string  | Torrent hash
+`state`_optional_      | string  | Torrent state
+"#;
+        let tree = md_parser::TokenTreeFactory::create(content).unwrap();
+
+        let groups = parse_groups(tree.children).unwrap();
+
+        // only changed values are returned, so `TorrentInfo` can not be used for the map values
+        let maindata = &groups[1].methods[0];
+        let objects = maindata.types.objects();
+        assert_eq!(
+            objects.iter().map(|o| o.name.as_str()).collect::<Vec<_>>(),
+            vec!["Torrents"]
+        );
+        let state = objects[0]
+            .types
+            .iter()
+            .find(|field| field.get_type_info().name == "state")
+            .unwrap();
+        assert!(matches!(state, types::Type::Enum(e) if e.name == "TorrentState"));
+        assert!(state.is_optional());
+        assert_eq!(
+            maindata.types.shared(),
+            vec![&SharedType::new(
+                "TorrentState",
+                "torrent_management",
+                "info"
+            )]
+        );
+    }
+
+    #[test]
+    fn a_table_without_a_title_is_reported_at_its_section() {
+        let content = r#"
+# Torrent management #
+
+All Torrent management API methods are under "torrents", e.g.: `/api/v2/torrents/methodName`.
+
+## Get torrent list ##
+Property    | Type    | Description
+------------|---------|------------
+`hash`      | string  | Torrent hash
+
+Name: `info`
+"#;
+        let tree = md_parser::TokenTreeFactory::create(content).unwrap();
+
+        let err = parse_groups(tree.children).unwrap_err();
+
+        assert_eq!(err.section.as_deref(), Some("Get torrent list"));
+        assert_eq!(err.line, Some(6));
+        assert!(err.expected.starts_with("a title"), "{err}");
+    }
+
+    #[test]
+    fn a_row_with_a_single_column_is_reported_at_its_line() {
+        let content = r#"
+# Torrent management #
+
+All Torrent management API methods are under "torrents", e.g.: `/api/v2/torrents/methodName`.
+
+## Pause torrents ##
+
+Name: `pause`
+
+**Parameters:**
+
+Parameter   | Type    | Description
+------------|---------|------------
+`hashes`    | string  | The hashes of the torrents you want to pause
+`foo`       |
+"#;
+        let tree = md_parser::TokenTreeFactory::create(content).unwrap();
+
+        let err = parse_groups(tree.children).unwrap_err();
+
+        assert_eq!(err.section.as_deref(), Some("Pause torrents"));
+        assert_eq!(err.line, Some(15));
+        assert_eq!(err.expected, "a type in the second column of the row `foo`");
+    }
 }
@@ -1,16 +1,4 @@
 use case::CaseExt;
-use regex::RegexBuilder;
-
-#[derive(Debug, Clone)]
-pub struct TypeDescriptions {
-    pub value: String,
-    pub description: String,
-}
-
-#[derive(Debug, Clone)]
-pub struct TypeDescription {
-    pub values: Vec<TypeDescriptions>,
-}
 
 #[derive(Debug, Clone)]
 pub struct TypeInfo {
@@ -44,20 +32,35 @@ pub struct Object {
 }
 
 #[derive(Debug, Clone)]
-pub struct Enum {
+pub struct EnumRef {
     pub type_info: TypeInfo,
-    pub values: Vec<EnumValue>,
-}
-
-#[derive(Debug, Clone)]
-pub struct EnumValue {
-    pub description: Option<String>,
-    pub key: String,
-    pub value: String,
+    pub name: String,
 }
 
 pub const OPTIONAL: &str = "_optional_";
 
+/// Integers are `i64` in order to not overflow, unless the API only allows a small range of values.
+const INTEGER_OVERRIDES: &[(&str, &str)] = &[
+    ("listen_port", "u16"),
+    ("proxy_port", "u16"),
+    ("web_ui_port", "u16"),
+    ("embedded_tracker_port", "u16"),
+    ("outgoing_ports_min", "u16"),
+    ("outgoing_ports_max", "u16"),
+    ("schedule_from_hour", "u8"),
+    ("schedule_from_min", "u8"),
+    ("schedule_to_hour", "u8"),
+    ("schedule_to_min", "u8"),
+];
+
+pub fn integer_type(name: &str) -> &'static str {
+    INTEGER_OVERRIDES
+        .iter()
+        .find(|(field, _)| *field == name)
+        .map(|(_, type_)| *type_)
+        .unwrap_or("i64")
+}
+
 #[derive(Debug, Clone)]
 pub enum Type {
     Number(TypeInfo),
@@ -65,18 +68,23 @@ pub enum Type {
     Bool(TypeInfo),
     String(TypeInfo),
     StringArray(TypeInfo),
+    /// A JSON object without a documented structure.
+    JsonObject(TypeInfo),
     Object(Object),
+    /// A value restricted to the values of a generated enum.
+    Enum(EnumRef),
 }
 
 impl Type {
     pub fn to_borrowed_type(&self) -> String {
         match self {
-            Type::Number(_) => "i32".into(),
-            Type::Float(_) => "f32".into(),
+            Type::Number(t) => integer_type(&t.name).into(),
+            Type::Float(_) => "f64".into(),
             Type::Bool(_) => "bool".into(),
             Type::String(_) => "str".into(),
             Type::StringArray(_) => "&[str]".into(),
-            Type::Object(_) => todo!(),
+            Type::JsonObject(_) | Type::Object(_) => todo!(),
+            Type::Enum(e) => e.name.clone(),
         }
     }
 
@@ -99,14 +107,58 @@ impl Type {
             Type::Bool(t) => t,
             Type::String(t) => t,
             Type::StringArray(t) => t,
+            Type::JsonObject(t) => t,
             Type::Object(t) => &t.type_info,
+            Type::Enum(t) => &t.type_info,
+        }
+    }
+
+    fn get_type_info_mut(&mut self) -> &mut TypeInfo {
+        match self {
+            Type::Number(t) => t,
+            Type::Float(t) => t,
+            Type::Bool(t) => t,
+            Type::String(t) => t,
+            Type::StringArray(t) => t,
+            Type::JsonObject(t) => t,
+            Type::Object(t) => &mut t.type_info,
+            Type::Enum(t) => &mut t.type_info,
+        }
+    }
+
+    pub fn to_enum(&self, name: &str) -> Type {
+        match self {
+            // e.g. `scan_dirs`, which maps directories to one of the enum values
+            Type::JsonObject(type_info) => Type::Object(Object {
+                type_info: type_info.clone(),
+                ref_type: RefType::Map("String".into(), name.into()),
+            }),
+            _ => Type::Enum(EnumRef {
+                type_info: self.get_type_info().clone(),
+                name: name.into(),
+            }),
         }
     }
 
+    pub fn to_bool(&self) -> Type {
+        Type::Bool(self.get_type_info().clone())
+    }
+
+    pub fn to_optional(&self) -> Type {
+        let mut type_ = self.clone();
+        type_.get_type_info_mut().is_optional = true;
+        type_
+    }
+
     pub fn from(type_as_str: &str, name: &str, description: Option<String>) -> Option<Type> {
         let type_name = match name.split_once(OPTIONAL) {
             Some((split, _)) => split,
             None => name,
+        };
+        // e.g. "index <sup>since 2.8.2</sup>"
+        let type_name = match type_name.split_once("<sup>") {
+            Some((split, _)) => split,
+            None => type_name,
         }
         .trim();
 
@@ -148,6 +200,10 @@ impl Type {
                 .and_then(create_object_type)
                 .or_else(|| Some(Type::StringArray(create_type_info()))),
             "float" => Some(Type::Float(create_type_info())),
+            "object" => description
+                .extract_type()
+                .and_then(create_object_type)
+                .or_else(|| Some(Type::JsonObject(create_type_info()))),
             name => description
                 .extract_type()
                 .and_then(create_object_type)
@@ -172,10 +228,7 @@ impl ExtractType for Option<String> {
     fn extract_type(&self) -> Option<RefType> {
         let list_type = || {
             self.as_ref().and_then(|t| {
-                let re = RegexBuilder::new(r"(?:Array|List) of (\w+) objects")
-                    .case_insensitive(true)
-                    .build()
-                    .unwrap();
+                let re = regex!(r"(?i)(?:Array|List) of (\w+) objects");
 
                 let cap = re.captures(t)?;
 
@@ -187,10 +240,7 @@ impl ExtractType for Option<String> {
 
         let map_type = || {
             self.as_ref().and_then(|t| {
-                let re = RegexBuilder::new(r"map from (\w+) to (\w+) object")
-                    .case_insensitive(true)
-                    .build()
-                    .unwrap();
+                let re = regex!(r"(?i)map from (\w+) to (\w+) object");
 
                 let cap = re.captures(t)?;
                 let key_type = match cap.get(1).map(|m| m.as_str().to_camel()) {
@@ -208,10 +258,7 @@ impl ExtractType for Option<String> {
 
         let object_type = || {
             self.as_ref().and_then(|t| {
-                let re = RegexBuilder::new(r"(\w+) object see table below")
-                    .case_insensitive(true)
-                    .build()
-                    .unwrap();
+                let re = regex!(r"(?i)(\w+) object see table below");
 
                 let cap = re.captures(t)?;
                 let object_type = match cap.get(1).map(|m| m.as_str().to_camel()) {
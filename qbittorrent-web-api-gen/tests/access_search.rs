@@ -9,7 +9,7 @@ struct Api {}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let api = Api::login(BASE_URL, USERNAME, PASSWORD).await?;
+    let api = Api::login(&base_url().await, USERNAME, PASSWORD).await?;
 
     let _ = api.search().delete(1).send().await?;
     let _ = api.search().plugins().await?;
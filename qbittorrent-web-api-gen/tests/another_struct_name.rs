@@ -9,7 +9,7 @@ struct Foo {}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let _ = Foo::login(BASE_URL, USERNAME, PASSWORD).await?;
+    let _ = Foo::login(&base_url().await, USERNAME, PASSWORD).await?;
 
     Ok(())
 }
@@ -0,0 +1,26 @@
+use anyhow::Result;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    struct Api {}
+}
+
+use foo::api_impl::torrent_management::piece_states::PieceState;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let states: Vec<PieceState> = serde_json::from_str("[0,2,1]")?;
+    assert_eq!(
+        states,
+        vec![
+            PieceState::NotDownloadedYet,
+            PieceState::AlreadyDownloaded,
+            PieceState::NowDownloading,
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use anyhow::Result;
+use common::*;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    pub struct Api {}
+}
+
+use foo::api_impl::sync::maindata::Torrents;
+use foo::api_impl::{Bytes, BytesPerSecond};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let torrent: Torrents = serde_json::from_str(
+        r#"{
+            "size": 1288490189,
+            "dlspeed": 358400
+        }"#,
+    )?;
+
+    let size = torrent.size.expect("size should be present");
+    assert_eq!(size, Bytes(1288490189));
+    assert_eq!(size.to_string(), "1.2 GiB");
+    assert_eq!(format!("{:.2}", size), "1.20 GiB");
+    assert_eq!(torrent.dlspeed, Some(BytesPerSecond(358400)));
+    assert_eq!(BytesPerSecond(358400).to_string(), "350.0 KiB/s");
+    assert_eq!(Bytes(512).to_string(), "512 B");
+    // `abs` would overflow in debug builds
+    assert_eq!(Bytes(i64::MIN).to_string(), "-8.0 EiB");
+    assert_eq!(Bytes(-2048).to_string(), "-2.0 KiB");
+    assert_eq!(BytesPerSecond(i64::MIN).to_string(), "unlimited");
+
+    assert_eq!("1024".parse::<Bytes>()?, Bytes(1024));
+    assert_eq!("1.5 KiB".parse::<Bytes>()?, Bytes(1536));
+    assert_eq!("2 MB".parse::<Bytes>()?, Bytes(2_000_000));
+    assert_eq!("350 KiB/s".parse::<BytesPerSecond>()?, BytesPerSecond(358400));
+    assert!("a lot".parse::<Bytes>().is_err());
+
+    let total: Bytes = vec![Bytes(1024), Bytes(2048)].into_iter().sum();
+    assert_eq!(total, Bytes(3072));
+    assert_eq!(total - Bytes(1024), Bytes(2048));
+    assert_eq!(Bytes(1024) * 2 + Bytes(1), Bytes(2049));
+    assert_eq!(BytesPerSecond(2048) / 2, BytesPerSecond(1024));
+
+    // speed limits which aren't applied
+    assert_eq!(BytesPerSecond::UNLIMITED.to_string(), "unlimited");
+    assert!(BytesPerSecond::UNLIMITED.is_unlimited());
+    assert!(BytesPerSecond(0).is_unlimited());
+    assert!(!BytesPerSecond(1024).is_unlimited());
+    assert_eq!(
+        "unlimited".parse::<BytesPerSecond>()?,
+        BytesPerSecond::UNLIMITED
+    );
+    assert!("unlimited".parse::<Bytes>().is_err());
+
+    // parameters and plain text responses use the same types as the response fields
+    let api = foo::Api::login(&base_url().await, USERNAME, PASSWORD).await?;
+    api.transfer_info()
+        .set_download_limit(BytesPerSecond(1024))
+        .await?;
+    let limit: BytesPerSecond = api.transfer_info().download_limit().await?;
+    assert!(limit.0 >= 0);
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use hyper::{
+    header,
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server, StatusCode,
+};
+use serde_json::Value;
+
+use super::{responses, PASSWORD, USERNAME};
+
+const SID: &str = "SID=mock-session";
+
+/// A stand-in for qBittorrent which serves canned responses, so the tests don't need a
+/// running instance or a network connection. It checks the credentials and the session
+/// cookie like qBittorrent does and remembers added torrents and changed preferences.
+pub struct MockServer {
+    url: String,
+}
+
+impl MockServer {
+    /// Starts the server on a free port of the current tokio runtime.
+    pub async fn start() -> MockServer {
+        let state = Arc::new(Mutex::new(State::default()));
+        let make_service = make_service_fn(move |_| {
+            let state = state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| handle(state.clone(), req))) }
+        });
+
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+
+        MockServer { url }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    preferences: Value,
+    torrents: Vec<Value>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            preferences: responses::preferences(),
+            torrents: Vec::new(),
+        }
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<State>>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let path = req.uri().path().to_string();
+    let is_authenticated = req
+        .headers()
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|cookie| cookie.to_str().ok())
+        .any(|cookie| cookie.split(';').any(|c| c.trim() == SID));
+    let content_type = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .unwrap_or_default();
+    let form = parse_form(&content_type, &String::from_utf8_lossy(&body));
+
+    let response = match path.as_str() {
+        "/api/v2/auth/login" => {
+            let is_valid = form.get("username").map(String::as_str) == Some(USERNAME)
+                && form.get("password").map(String::as_str) == Some(PASSWORD);
+            if is_valid {
+                Response::builder()
+                    .header(header::SET_COOKIE, format!("{SID}; HttpOnly; path=/"))
+                    .body(Body::from("Ok."))
+            } else {
+                Response::builder().body(Body::from("Fails."))
+            }
+        }
+        _ if !is_authenticated => Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Body::from("Forbidden")),
+        path => {
+            let mut state = state.lock().unwrap();
+            match respond(&mut state, path, &form) {
+                Some(body) => Response::builder().body(Body::from(body)),
+                None => Response::builder()
+                    .status(StatusCode::NOT_FOUND)
+                    .body(Body::from("Not Found")),
+            }
+        }
+    };
+
+    Ok(response.expect("a valid response"))
+}
+
+/// The body of the response, `None` if there is no such method.
+fn respond(state: &mut State, path: &str, form: &HashMap<String, String>) -> Option<String> {
+    let (group, method) = path.strip_prefix("/api/v2/")?.split_once('/')?;
+
+    match (group, method) {
+        ("app", "preferences") => Some(state.preferences.to_string()),
+        ("app", "setPreferences") => {
+            let patch: Value = serde_json::from_str(form.get("json")?).ok()?;
+            if let (Some(preferences), Some(patch)) =
+                (state.preferences.as_object_mut(), patch.as_object())
+            {
+                preferences.extend(patch.clone());
+            }
+            Some(String::new())
+        }
+        ("torrents", "add") => {
+            let urls = form.get("urls").map(String::as_str).unwrap_or_default();
+            for url in urls.lines().filter(|url| !url.is_empty()) {
+                state.torrents.push(responses::torrent(url));
+            }
+            Some(String::new())
+        }
+        ("torrents", "info") => Some(Value::from(state.torrents.clone()).to_string()),
+        // the limit of each requested torrent, e.g. `hashes=abc|def`
+        ("torrents", "downloadLimit" | "uploadLimit") => {
+            let limits: serde_json::Map<String, Value> = form
+                .get("hashes")?
+                .split('|')
+                .map(|hash| (hash.to_string(), Value::from(338944)))
+                .collect();
+            Some(Value::from(limits).to_string())
+        }
+        _ => responses::canned(group, method),
+    }
+}
+
+/// The fields of a `multipart/form-data` or `application/x-www-form-urlencoded` body.
+fn parse_form(content_type: &str, body: &str) -> HashMap<String, String> {
+    let boundary = match content_type.split_once("boundary=") {
+        Some((_, boundary)) => boundary.trim_matches('"'),
+        None => {
+            return body
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        }
+    };
+
+    body.split(&format!("--{boundary}"))
+        .filter_map(|part| {
+            let (headers, value) = part.split_once("\r\n\r\n")?;
+            let (_, name) = headers.split_once("name=\"")?;
+            let (name, _) = name.split_once('"')?;
+
+            Some((name.to_string(), value.trim_end_matches("\r\n").to_string()))
+        })
+        .collect()
+}
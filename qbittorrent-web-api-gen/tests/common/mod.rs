@@ -1,3 +1,18 @@
+#![allow(dead_code)]
+
+mod mock;
+mod responses;
+
+pub use mock::MockServer;
+
 pub const USERNAME: &str = "admin";
 pub const PASSWORD: &str = "adminadmin";
-pub const BASE_URL: &str = "http://localhost:8080";
+
+/// The url of the qBittorrent instance to test against. A mock server is started unless
+/// `QBITTORRENT_URL` points to a real instance, e.g. `http://localhost:8080`.
+pub async fn base_url() -> String {
+    match std::env::var("QBITTORRENT_URL") {
+        Ok(url) => url,
+        Err(_) => MockServer::start().await.url().to_string(),
+    }
+}
@@ -0,0 +1,219 @@
+use serde_json::{json, Value};
+
+/// The canned response of a method, e.g. the build info of `app/buildInfo`. Methods which
+/// don't return anything, e.g. `torrents/pause`, respond with an empty body.
+pub fn canned(group: &str, method: &str) -> Option<String> {
+    let response = match (group, method) {
+        ("auth", "logout") => json!(null),
+        ("app", "version") => return Some("v4.4.5".into()),
+        ("app", "webapiVersion") => return Some("2.8.3".into()),
+        ("app", "buildInfo") => json!({
+            "qt": "5.15.2",
+            "libtorrent": "1.2.15.0",
+            "boost": "1.76.0",
+            "openssl": "1.1.1m",
+            "bitness": 64,
+        }),
+        ("app", "defaultSavePath") => return Some("/downloads".into()),
+        ("app", "shutdown") => json!(null),
+        ("log", "main") => json!([{
+            "id": 0,
+            "message": "qBittorrent v4.4.5 started",
+            "timestamp": 1660000000,
+            "type": 1,
+        }]),
+        ("log", "peers") => json!([{
+            "id": 0,
+            "ip": "127.0.0.1",
+            "timestamp": 1660000000,
+            "blocked": false,
+            "reason": "",
+        }]),
+        ("sync", "maindata") => json!({
+            "rid": 1,
+            "full_update": true,
+            "torrents": {},
+            "categories": categories(),
+            "tags": ["linux"],
+            "server_state": {
+                "average_time_queue": 0,
+                "dl_info_data": 0,
+                "dl_info_speed": 0,
+                "queued_io_jobs": 0,
+                "total_buffers_size": 0,
+                "total_peer_connections": 0,
+            },
+        }),
+        ("sync", "torrentPeers") => json!({ "rid": 1, "full_update": true, "peers": {} }),
+        ("transfer", "info") => json!({
+            "dl_info_speed": 0,
+            "dl_info_data": 0,
+            "up_info_speed": 0,
+            "up_info_data": 0,
+            "dl_rate_limit": 0,
+            "up_rate_limit": 0,
+            "dht_nodes": 0,
+            "connection_status": "connected",
+        }),
+        ("transfer", "speedLimitsMode") => return Some("0".into()),
+        ("transfer", "downloadLimit" | "uploadLimit") => return Some("0".into()),
+        ("transfer", _) => json!(null),
+        ("torrents", "properties") => json!({
+            "save_path": "/downloads",
+            "creation_date": 1660000000,
+            "piece_size": 16384,
+            "comment": "",
+            "total_wasted": 0,
+            "total_uploaded": 0,
+            "total_uploaded_session": 0,
+            "total_downloaded": 0,
+            "total_downloaded_session": 0,
+            "up_limit": -1,
+            "dl_limit": -1,
+            "time_elapsed": 0,
+            "seeding_time": 0,
+            "nb_connections": 0,
+            "nb_connections_limit": 100,
+            "share_ratio": 0.0,
+            "addition_date": 1660000000,
+            "completion_date": -1,
+            "created_by": "",
+            "dl_speed_avg": 0,
+            "dl_speed": 0,
+            "eta": 8640000,
+            "last_seen": -1,
+            "peers": 0,
+            "peers_total": 0,
+            "pieces_have": 0,
+            "pieces_num": 1,
+            "reannounce": 0,
+            "seeds": 0,
+            "seeds_total": 0,
+            "total_size": 16384,
+            "up_speed_avg": 0,
+            "up_speed": 0,
+        }),
+        ("torrents", "trackers") => json!([{
+            "url": "** [DHT] **",
+            "status": 2,
+            "tier": -1,
+            "num_peers": 0,
+            "num_seeds": 0,
+            "num_leeches": 0,
+            "num_downloaded": 0,
+            "msg": "",
+        }]),
+        ("torrents", "webseeds") => json!([{ "url": "http://example.com/file" }]),
+        ("torrents", "files") => json!([{
+            "index": 0,
+            "name": "file",
+            "size": 16384,
+            "progress": 0.0,
+            "priority": 1,
+            "is_seed": false,
+            "piece_range": [0, 0],
+            "availability": 0.0,
+        }]),
+        ("torrents", "pieceStates") => json!([0]),
+        ("torrents", "pieceHashes") => json!(["8c3f0b5d2a1e4f6a7b9c0d1e2f3a4b5c6d7e8f90"]),
+        ("torrents", "categories") => categories(),
+        ("torrents", "tags") => json!(["linux"]),
+        ("torrents", _) => json!(null),
+        ("rss", "items" | "rules") => json!({}),
+        ("rss", "matchingArticles") => json!({}),
+        ("rss", _) => json!(null),
+        ("search", "start") => json!({ "id": 1 }),
+        ("search", "status") => json!([{ "id": 1, "status": "Stopped", "total": 0 }]),
+        ("search", "results") => json!({ "results": [], "status": "Stopped", "total": 0 }),
+        ("search", "plugins") => json!([{
+            "enabled": true,
+            "fullName": "Legit Torrents",
+            "name": "legittorrents",
+            "supportedCategories": [{ "id": "all", "name": "All categories" }],
+            "url": "http://www.legittorrents.info",
+            "version": "2.3",
+        }]),
+        ("search", _) => json!(null),
+        _ => return None,
+    };
+
+    Some(match response {
+        Value::Null => String::new(),
+        response => response.to_string(),
+    })
+}
+
+/// The preferences before they are changed by `app/setPreferences`.
+pub fn preferences() -> Value {
+    json!({
+        "locale": "en",
+        "save_path": "/downloads",
+        "dht": false,
+        "pex": true,
+        "lsd": true,
+        "listen_port": 6881,
+        "web_ui_port": 8080,
+        "web_ui_username": "admin",
+    })
+}
+
+/// A torrent added by `torrents/add`.
+pub fn torrent(url: &str) -> Value {
+    let mut torrent: Value = serde_json::from_str(TORRENT).expect("valid json");
+    torrent["magnet_uri"] = url.into();
+
+    torrent
+}
+
+// too many fields for `json!`
+const TORRENT: &str = r#"{
+    "added_on": 1660000000,
+    "amount_left": 16384,
+    "auto_tmm": false,
+    "availability": 0.0,
+    "category": "",
+    "completed": 0,
+    "completion_on": -1,
+    "content_path": "/downloads/file",
+    "dl_limit": -1,
+    "dlspeed": 0,
+    "downloaded": 0,
+    "downloaded_session": 0,
+    "eta": 8640000,
+    "f_l_piece_prio": false,
+    "force_start": false,
+    "hash": "5cc013e801095be61d768e609e3039da58616fd0",
+    "last_activity": 0,
+    "magnet_uri": "",
+    "max_ratio": -1.0,
+    "max_seeding_time": -1,
+    "name": "file",
+    "num_complete": 0,
+    "num_incomplete": 0,
+    "num_leechs": 0,
+    "num_seeds": 0,
+    "priority": 1,
+    "progress": 0.0,
+    "ratio": 0.0,
+    "ratio_limit": -2.0,
+    "save_path": "/downloads",
+    "seeding_time": 0,
+    "seeding_time_limit": -2,
+    "seen_complete": -1,
+    "seq_dl": false,
+    "size": 16384,
+    "state": "downloading",
+    "super_seeding": false,
+    "tags": "",
+    "time_active": 0,
+    "total_size": 16384,
+    "tracker": "",
+    "up_limit": -1,
+    "uploaded": 0,
+    "uploaded_session": 0,
+    "upspeed": 0
+}"#;
+
+fn categories() -> Value {
+    json!({ "linux": { "name": "linux", "savePath": "/downloads/linux" } })
+}
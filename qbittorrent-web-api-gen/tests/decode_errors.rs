@@ -0,0 +1,86 @@
+use anyhow::Result;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[derive(QBittorrentApiGen)]
+    pub struct Api {}
+}
+
+use foo::{api_impl::Error, Api};
+
+const TRACKERS: &str = r#"[
+{"url": "http://tracker.example.org/announce", "status": 2, "tier": 0, "num_peers": 1, "num_seeds": 1, "num_leeches": 0, "num_downloaded": 3, "msg": ""},
+{"url": "http://tracker.example.net/announce", "status": 2, "tier": 1, "num_peers": "many", "num_seeds": 1, "num_leeches": 0, "num_downloaded": 3, "msg": ""}
+]"#;
+
+/// Answers every request with a session cookie and the trackers above, except for
+/// `torrents/files` which fails with a non-JSON error page.
+async fn serve(listener: TcpListener) -> Result<()> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let mut request = vec![0; 64 * 1024];
+        let read = stream.read(&mut request).await?;
+
+        let (status, body) = if request[..read].starts_with(b"POST /api/v2/torrents/files ") {
+            ("404 Not Found", "Not Found")
+        } else {
+            ("200 OK", TRACKERS)
+        };
+        let response = format!(
+            "HTTP/1.1 {}\r\nset-cookie: SID=test; path=/\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await?;
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let base_url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(serve(listener));
+
+    let api = Api::login(&base_url, "admin", "adminadmin").await?;
+    let err = api
+        .torrent_management()
+        .trackers("8c212779b4abde7c6bc608063a0d008b7e40ce32")
+        .await
+        .expect_err("`num_peers` should not be decoded from a string");
+
+    match err {
+        Error::Decode {
+            endpoint,
+            path,
+            snippet,
+            ..
+        } => {
+            assert_eq!(endpoint, "/api/v2/torrents/trackers");
+            assert_eq!(path, "[1].num_peers");
+            assert!(snippet.contains(r#""num_peers": "many""#), "{snippet}");
+        }
+        err => panic!("expected a decode error, got: {err}"),
+    }
+
+    let err = api
+        .torrent_management()
+        .files("8c212779b4abde7c6bc608063a0d008b7e40ce32")
+        .send()
+        .await
+        .expect_err("a 404 should not be decoded");
+
+    match err {
+        Error::HttpError(err) => {
+            assert_eq!(err.status(), Some(reqwest::StatusCode::NOT_FOUND));
+        }
+        err => panic!("expected an http error, got: {err}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+mod common;
+
+use anyhow::Result;
+use common::*;
+use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+#[derive(QBittorrentApiGen)]
+struct Api {}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    use api_impl::torrent_management::info::{TorrentFilter, TorrentSort};
+
+    let api = Api::login(&base_url().await, USERNAME, PASSWORD).await?;
+
+    let _ = api
+        .torrent_management()
+        .info()
+        .filter(TorrentFilter::Downloading)
+        .sort(TorrentSort::AddedOn)
+        .send()
+        .await?;
+
+    Ok(())
+}
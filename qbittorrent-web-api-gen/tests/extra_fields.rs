@@ -0,0 +1,31 @@
+use anyhow::Result;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    struct Api {}
+}
+
+use foo::api_impl::types::BuildInfo;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let build_info: BuildInfo = serde_json::from_str(
+        r#"{
+            "qt": "5.15.2",
+            "libtorrent": "1.2.15.0",
+            "boost": "1.76.0",
+            "openssl": "1.1.1l",
+            "zlib": "1.2.11",
+            "bitness": 64
+        }"#,
+    )?;
+
+    assert_eq!(build_info.bitness, 64);
+    assert_eq!(build_info.extra.len(), 1);
+    assert_eq!(build_info.extra["zlib"], "1.2.11");
+
+    Ok(())
+}
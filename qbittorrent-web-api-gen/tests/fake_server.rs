@@ -0,0 +1,69 @@
+use anyhow::Result;
+use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+#[derive(QBittorrentApiGen)]
+struct Api {}
+
+use api_impl::fake::{FakeServer, METHODS};
+
+const HASH: &str = "5cc013e801095be61d768e609e3039da58616fd0";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let server = FakeServer::start().await;
+    assert!(Api::login(server.url(), FakeServer::USERNAME, "wrong password")
+        .await
+        .is_err());
+
+    let api = Api::login(server.url(), FakeServer::USERNAME, FakeServer::PASSWORD).await?;
+
+    // the samples are accepted by the client
+    let _ = api.application().version().await?;
+    let _ = api.application().build_info().await?;
+    let _ = api.log().main().send().await?;
+    let _ = api.sync().maindata(0).await?;
+    let _ = api.transfer_info().speed_limits_mode().await?;
+    let _ = api.torrent_management().info().send().await?;
+    let _ = api.torrent_management().properties(HASH).await?;
+    let _ = api.torrent_management().trackers(HASH).await?;
+    let _ = api.torrent_management().categories().await?;
+    let _ = api.search().results(1).send().await?;
+    let _ = api.search().plugins().await?;
+    api.torrent_management().pause(HASH).await?;
+
+    // scripted responses
+    server.respond("/api/v2/app/version", "v4.4.5");
+    assert_eq!(api.application().version().await?.to_string(), "4.4.5");
+    server.fail("/api/v2/torrents/properties", 404);
+    assert!(api.torrent_management().properties(HASH).await.is_err());
+
+    let requests = server.requests();
+    let pause = requests
+        .iter()
+        .find(|request| request.path == "/api/v2/torrents/pause")
+        .unwrap();
+    assert_eq!(pause.form["hashes"], HASH);
+
+    // the parameters are validated
+    let info = METHODS
+        .iter()
+        .find(|method| method.path == "/api/v2/torrents/properties")
+        .unwrap();
+    assert!(info.parameters.iter().any(|p| p.name == "hash" && p.is_mandatory));
+
+    let client = reqwest::Client::new();
+    let post = |form: &'static [(&'static str, &'static str)]| {
+        client
+            .post(format!("{}/api/v2/torrents/pause", server.url()))
+            .header("cookie", "SID=fake-session")
+            .form(form)
+            .send()
+    };
+    assert_eq!(post(&[]).await?.status(), 400);
+    assert_eq!(post(&[("hashes", HASH), ("foo", "bar")]).await?.status(), 400);
+    assert_eq!(post(&[("hashes", HASH)]).await?.status(), 200);
+
+    api.logout().await?;
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anyhow::Result;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    struct Api {}
+}
+
+use foo::api_impl::types::{BuildInfo, FilePriority, TorrentState, TrackerStatus};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let build_info: BuildInfo = serde_json::from_str(
+        r#"{"qt": "5.15.2", "libtorrent": "1.2.15.0", "boost": "1.76.0", "openssl": "1.1.1l", "bitness": 64}"#,
+    )?;
+    assert_eq!(build_info.bitness, 64);
+
+    let state: TorrentState = serde_json::from_str(r#""pausedUP""#)?;
+    assert_eq!(state, TorrentState::PausedUP);
+
+    let status: TrackerStatus = serde_json::from_str("2")?;
+    assert_eq!(status, TrackerStatus::TrackerHasBeenContactedAndIsWorking);
+
+    // the flat module re-exports the types of the method modules
+    let priority: foo::api_impl::torrent_management::files::FilePriority = FilePriority::MaximalPriority;
+    assert_eq!(priority, FilePriority::MaximalPriority);
+
+    Ok(())
+}
@@ -9,7 +9,7 @@ struct Api {}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let _ = Api::login(BASE_URL, USERNAME, PASSWORD).await?;
+    let _ = Api::login(&base_url().await, USERNAME, PASSWORD).await?;
 
     Ok(())
 }
@@ -0,0 +1,41 @@
+mod common;
+
+use anyhow::Result;
+use common::*;
+use std::collections::HashMap;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    pub struct Api {}
+}
+
+use foo::api_impl::torrent_management::categories::Categories;
+use foo::api_impl::BytesPerSecond;
+
+const HASH: &str = "8c212779b4abde7c6bc608063a0d008b7e40ce32";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let categories: HashMap<String, Categories> = serde_json::from_str(
+        r#"{
+            "Video": {
+                "name": "Video",
+                "savePath": "/home/user/torrents/video/"
+            }
+        }"#,
+    )?;
+    assert_eq!(categories["Video"].save_path, "/home/user/torrents/video/");
+
+    // keyed by the `hashes` which are sent
+    let api = foo::Api::login(&base_url().await, USERNAME, PASSWORD).await?;
+    let limits: HashMap<String, BytesPerSecond> =
+        api.torrent_management().download_limit(HASH).await?;
+    assert!(limits.contains_key(HASH));
+    let limits = api.torrent_management().upload_limit(HASH).await?;
+    assert!(limits.contains_key(HASH));
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use anyhow::Result;
+use common::*;
+use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+#[derive(QBittorrentApiGen)]
+struct Api {}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let server = MockServer::start().await;
+    assert!(Api::login(server.url(), USERNAME, "wrong password")
+        .await
+        .is_err());
+
+    let api = Api::login(server.url(), USERNAME, PASSWORD).await?;
+
+    // one method of each group
+    let _ = api.application().build_info().await?;
+    let _ = api.log().main().send().await?;
+    let _ = api.sync().maindata(0).await?;
+    let _ = api.transfer_info().info().await?;
+    let _ = api.torrent_management().categories().await?;
+    let _ = api.rss().items().send().await?;
+    let _ = api.search().plugins().await?;
+
+    api.logout().await?;
+
+    Ok(())
+}
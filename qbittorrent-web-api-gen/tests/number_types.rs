@@ -0,0 +1,32 @@
+use anyhow::Result;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    struct Api {}
+}
+
+use foo::api_impl::application::set_preferences::PreferencesPatch;
+use foo::api_impl::sync::maindata::Torrents;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let patch = PreferencesPatch {
+        listen_port: Some(u16::MAX),
+        schedule_from_hour: Some(23u8),
+        max_ratio: Some(1.25f64),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_string(&patch)?,
+        r#"{"max_ratio":1.25,"listen_port":65535,"schedule_from_hour":23}"#
+    );
+
+    let torrent: Torrents = serde_json::from_str(r#"{ "progress": 0.1, "priority": 3000000000 }"#)?;
+    assert_eq!(torrent.progress, Some(0.1f64));
+    assert_eq!(torrent.priority, Some(3_000_000_000i64));
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anyhow::Result;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    struct Api {}
+}
+
+use foo::api_impl::application::preferences::ScanDirs;
+use foo::api_impl::torrent_management::files::FilePriority;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let priorities: Vec<FilePriority> = serde_json::from_str("[0, 1, 6, 7]")?;
+    assert_eq!(
+        priorities,
+        vec![
+            FilePriority::DoNotDownload,
+            FilePriority::NormalPriority,
+            FilePriority::HighPriority,
+            FilePriority::MaximalPriority,
+        ]
+    );
+    assert_eq!(serde_json::to_string(&priorities)?, "[0,1,6,7]");
+
+    let scan_dirs: Vec<ScanDirs> = serde_json::from_str(r#"[0, 1, "/downloads"]"#)?;
+    assert_eq!(
+        scan_dirs,
+        vec![
+            ScanDirs::DownloadToTheMonitoredFolder,
+            ScanDirs::DownloadToTheDefaultSavePath,
+            ScanDirs::PathToDownloadTo("/downloads".into()),
+        ]
+    );
+    assert_eq!(serde_json::to_string(&scan_dirs)?, r#"[0,1,"/downloads"]"#);
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+mod common;
+
+use anyhow::Result;
+use common::*;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    // relative to the crate trybuild generates in `target/tests/qbittorrent-web-api-gen`
+    #[derive(QBittorrentApiGen)]
+    #[api_gen(openapi = "../../../qbittorrent-web-api-gen/tests/openapi/transfer.json")]
+    pub struct Api {}
+}
+
+use foo::{api_impl::BytesPerSecond, Api};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let api = Api::login(&base_url().await, USERNAME, PASSWORD).await?;
+
+    api.transfer_info()
+        .set_download_limit(BytesPerSecond(1024))
+        .await?;
+    let _: bool = api.transfer_info().speed_limits_mode().await?;
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[derive(QBittorrentApiGen)]
+    #[api_gen(openapi = "../../../qbittorrent-web-api-gen/tests/openapi/not_openapi.json")]
+    pub struct Api {}
+}
+
+fn main() {}
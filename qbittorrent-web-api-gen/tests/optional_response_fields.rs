@@ -0,0 +1,33 @@
+use anyhow::Result;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    struct Api {}
+}
+
+use foo::api_impl::sync::maindata::{MainData, TorrentState};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let partial: MainData = serde_json::from_str(
+        r#"{
+            "rid": 15,
+            "torrents": {
+                "8c212779b4abde7c6bc608063a0d008b7e40ce32": { "state": "pausedUP" }
+            }
+        }"#,
+    )?;
+
+    assert_eq!(partial.rid, 15);
+    assert_eq!(partial.full_update, None);
+    assert!(partial.server_state.is_none());
+    let torrents = partial.torrents.expect("torrents should be present");
+    let torrent = &torrents["8c212779b4abde7c6bc608063a0d008b7e40ce32"];
+    assert_eq!(torrent.name, None);
+    assert_eq!(torrent.state, Some(TorrentState::PausedUP));
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+mod common;
+
+use anyhow::Result;
+use common::*;
+use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+#[derive(QBittorrentApiGen)]
+struct Api {}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let api = Api::login(&base_url().await, USERNAME, PASSWORD).await?;
+
+    let patch = api_impl::application::set_preferences::PreferencesPatch {
+        dht: Some(true),
+        ..Default::default()
+    };
+    let _ = api.application().set_preferences(&patch).await?;
+
+    let preferences = api.application().preferences().await?;
+    assert_eq!(preferences.dht, Some(true));
+
+    Ok(())
+}
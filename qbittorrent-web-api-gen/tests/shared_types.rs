@@ -0,0 +1,41 @@
+use anyhow::Result;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    struct Api {}
+}
+
+use foo::api_impl::{application, sync, torrent_management};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // `torrents` of `sync/maindata` is documented as "same as torrent list"
+    let maindata: sync::maindata::MainData = serde_json::from_str(
+        r#"{
+            "rid": 15,
+            "torrents": {
+                "8c212779b4abde7c6bc608063a0d008b7e40ce32": { "state": "pausedUP" }
+            }
+        }"#,
+    )?;
+    let torrents = maindata.torrents.expect("torrents should be present");
+    let state: &Option<torrent_management::info::TorrentState> =
+        &torrents["8c212779b4abde7c6bc608063a0d008b7e40ce32"].state;
+    assert_eq!(
+        state,
+        &Some(torrent_management::info::TorrentState::PausedUP)
+    );
+
+    // types used by several methods are the same type
+    let protocol: application::preferences::BittorrentProtocol =
+        application::set_preferences::BittorrentProtocol::TCP;
+    assert_eq!(protocol, application::preferences::BittorrentProtocol::TCP);
+
+    let _: torrent_management::files::FilePriority =
+        torrent_management::file_prio::FilePriority::DoNotDownload;
+
+    Ok(())
+}
@@ -10,6 +10,8 @@ fn tests() {
     t.pass("tests/without_parameters.rs");
     // t.pass("tests/with_parameters.rs");
     t.pass("tests/default_parameters.rs");
+    t.pass("tests/set_preferences.rs");
+    t.pass("tests/enum_parameters.rs");
 
     // --- Return types ---
     t.pass("tests/return_type.rs");
@@ -20,5 +22,32 @@ fn tests() {
     t.pass("tests/add_torrent.rs");
     t.pass("tests/another_struct_name.rs");
     t.pass("tests/access_impl_types.rs");
+    t.pass("tests/numeric_enums.rs");
+    t.pass("tests/unknown_enum_values.rs");
+    t.pass("tests/optional_response_fields.rs");
+    t.pass("tests/bytes.rs");
+    t.pass("tests/number_types.rs");
+    t.pass("tests/version.rs");
+    t.pass("tests/array_responses.rs");
+    t.pass("tests/map_responses.rs");
     t.pass("tests/search_types.rs");
+    t.pass("tests/flat_types.rs");
+    t.pass("tests/shared_types.rs");
+    t.pass("tests/decode_errors.rs");
+    t.pass("tests/mock_server.rs");
+
+    // --- OpenAPI documents ---
+    t.pass("tests/openapi_document.rs");
+    t.compile_fail("tests/openapi_invalid_document.rs");
+
+    // --- Features ---
+    #[cfg(feature = "chrono")]
+    t.pass("tests/chrono_timestamps.rs");
+    // `chrono` wins when both are enabled
+    #[cfg(all(feature = "time", not(feature = "chrono")))]
+    t.pass("tests/time_timestamps.rs");
+    #[cfg(feature = "extra-fields")]
+    t.pass("tests/extra_fields.rs");
+    #[cfg(feature = "fake-server")]
+    t.pass("tests/fake_server.rs");
 }
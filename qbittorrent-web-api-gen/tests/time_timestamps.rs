@@ -0,0 +1,35 @@
+use anyhow::Result;
+use std::time::Duration;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    struct Api {}
+}
+
+use foo::api_impl::sync::maindata::Torrents;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let torrent: Torrents = serde_json::from_str(
+        r#"{
+            "added_on": 1438429165,
+            "completion_on": -1,
+            "eta": 8640000,
+            "seeding_time": 120
+        }"#,
+    )?;
+
+    assert_eq!(
+        torrent.added_on,
+        Some(time::OffsetDateTime::from_unix_timestamp(1438429165)?)
+    );
+    assert_eq!(torrent.completion_on, None);
+    assert_eq!(torrent.eta, None);
+    assert_eq!(torrent.seeding_time, Some(Duration::from_secs(120)));
+    assert_eq!(torrent.seen_complete, None);
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anyhow::Result;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    struct Api {}
+}
+
+use foo::api_impl::torrent_management::files::FilePriority;
+use foo::api_impl::torrent_management::info::TorrentState;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let states: Vec<TorrentState> = serde_json::from_str(r#"["uploading", "stoppedUP", "unknown"]"#)?;
+    assert_eq!(
+        states,
+        vec![
+            TorrentState::Uploading,
+            TorrentState::Unknown("stoppedUP".into()),
+            TorrentState::Unknown("unknown".into()),
+        ]
+    );
+    assert_eq!(
+        serde_json::to_string(&states)?,
+        r#"["uploading","stoppedUP","unknown"]"#
+    );
+    assert_eq!(TorrentState::Unknown("stoppedUP".into()).to_string(), "stoppedUP");
+
+    let priority: FilePriority = serde_json::from_str("3")?;
+    assert_eq!(priority, FilePriority::Unknown(3));
+    assert_eq!(serde_json::to_string(&priority)?, "3");
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anyhow::Result;
+
+mod foo {
+    use qbittorrent_web_api_gen::QBittorrentApiGen;
+
+    #[allow(dead_code)]
+    #[derive(QBittorrentApiGen)]
+    struct Api {}
+}
+
+use foo::api_impl::Version;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let version: Version = "v4.1.3".parse()?;
+    assert_eq!(
+        version,
+        Version {
+            major: 4,
+            minor: 1,
+            patch: 3
+        }
+    );
+    assert_eq!(version.to_string(), "4.1.3");
+
+    let webapi_version: Version = "2.0".parse()?;
+    assert_eq!((webapi_version.major, webapi_version.minor), (2, 0));
+    assert!(webapi_version < "2.8.3".parse()?);
+    assert_eq!("v4.4.0beta1".parse::<Version>()?.patch, 0);
+    assert!("unknown".parse::<Version>().is_err());
+
+    Ok(())
+}
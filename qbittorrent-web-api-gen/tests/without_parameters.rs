@@ -9,11 +9,11 @@ struct Api {}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let api = Api::login(BASE_URL, USERNAME, PASSWORD).await?;
+    let api = Api::login(&base_url().await, USERNAME, PASSWORD).await?;
     let version = api.application().version().await?;
 
     // don't be too specific
-    assert!(version.starts_with("v4.4"), "got: {}", version);
+    assert_eq!((version.major, version.minor), (4, 4), "got: {}", version);
 
     Ok(())
 }